@@ -0,0 +1,127 @@
+use super::mirror_coordinates;
+use crate::components::{Cell, WrappingCell};
+use bevy::prelude::Component;
+use std::ops::Deref;
+
+/// One-dimensional cell, its neighbors are every cell within the `R` radius on
+/// both sides. It uses `i32` coordinates.
+///
+/// The neighbors are ordered from left to right, the center cell excluded.
+///
+/// ```ascii
+/// R = 1                  R = 2
+/// +----+---+---+         +----+----+---+---+---+
+/// | -1 | 0 | 1 |         | -2 | -1 | 0 | 1 | 2 |
+/// +----+---+---+         +----+----+---+---+---+
+/// ```
+///
+/// Elementary cellular automata, like the Wolfram rules, use a `Cell1d<1>`
+/// which is the default radius.
+#[derive(Debug, Clone, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct Cell1d<const R: i32 = 1> {
+    /// The 1D cell coordinate
+    pub coords: i32,
+}
+
+impl<const R: i32> Deref for Cell1d<R> {
+    type Target = i32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.coords
+    }
+}
+
+impl<const R: i32> Cell for Cell1d<R> {
+    type Coordinates = i32;
+
+    #[inline]
+    fn coords(&self) -> &Self::Coordinates {
+        &self.coords
+    }
+
+    #[inline]
+    fn neighbor_coordinates(&self) -> impl ExactSizeIterator<Item = Self::Coordinates> + '_ {
+        (-R..R).map(move |i| self.coords + Self::neighbor_offset(i + R))
+    }
+
+    #[inline]
+    fn mirrored_coordinates(
+        &self,
+        neighbor: &Self::Coordinates,
+        contains: impl Fn(&Self::Coordinates) -> bool,
+    ) -> Option<Self::Coordinates> {
+        mirror_coordinates([self.coords], [*neighbor], |[c]| contains(&c)).map(|[c]| c)
+    }
+}
+
+impl<const R: i32> WrappingCell for Cell1d<R> {
+    type Dimensions = i32;
+
+    #[inline]
+    fn wrap(coordinates: Self::Coordinates, dimensions: &Self::Dimensions) -> Self::Coordinates {
+        coordinates.rem_euclid(*dimensions)
+    }
+}
+
+impl<const R: i32> Cell1d<R> {
+    /// Amount of neighbors
+    #[allow(clippy::cast_sign_loss)]
+    pub const NEIGHBOR_COUNT: usize = 2 * R as usize;
+
+    /// Instantiates a new cell with `coords` value
+    #[must_use]
+    #[inline]
+    pub const fn new(coords: i32) -> Self {
+        Self { coords }
+    }
+
+    /// Neighbor coordinate offset of the neighbor `index`, in the
+    /// [`Cell::neighbor_coordinates`] order. The neighbor indices are given to
+    /// [`CellState::new_cell_state_positional`](crate::CellState::new_cell_state_positional)
+    #[must_use]
+    #[inline]
+    pub const fn neighbor_offset(index: i32) -> i32 {
+        if index < R {
+            index - R
+        } else {
+            index - R + 1
+        }
+    }
+}
+
+/// Cell of a 1D automaton space-time diagram, recording the state of the
+/// [`Cell1d`] at `coords` for the given `generation`.
+///
+/// Spawned by the [`History1dPlugin`](crate::History1dPlugin) along with the
+/// recorded state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct HistoryCell {
+    /// The recorded cell coordinate
+    pub coords: i32,
+    /// The recorded generation
+    pub generation: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correct_coordinates() {
+        let cell = Cell1d::<1>::new(10);
+        let neighbors: Vec<_> = cell.neighbor_coordinates().collect();
+        assert_eq!(neighbors, vec![9, 11]);
+        let cell = Cell1d::<3>::new(-2);
+        let neighbors = cell.neighbor_coordinates();
+        assert_eq!(neighbors.len(), Cell1d::<3>::NEIGHBOR_COUNT);
+        assert_eq!(neighbors.collect::<Vec<_>>(), vec![-5, -4, -3, -1, 0, 1]);
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(Cell1d::<1>::wrap(-1, &10), 9);
+        assert_eq!(Cell1d::<1>::wrap(10, &10), 0);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::components::Cell;
-use bevy::prelude::{Component, IVec3};
+use crate::components::{Cell, WrappingCell};
+use bevy::prelude::{Component, IVec2, IVec3};
 use std::ops::Deref;
 
 const NEIGHBOR_COORDINATES: [IVec3; 6] = [
@@ -63,7 +63,24 @@ impl Cell for HexagonCell2d {
     }
 }
 
+/// The world dimensions are the amount of columns (`x` axis) and rows (`z`
+/// axis), forming a rhombus shaped world
+impl WrappingCell for HexagonCell2d {
+    type Dimensions = IVec2;
+
+    #[inline]
+    fn wrap(coordinates: Self::Coordinates, dimensions: &Self::Dimensions) -> Self::Coordinates {
+        let x = coordinates.x.rem_euclid(dimensions.x);
+        let z = coordinates.z.rem_euclid(dimensions.y);
+        IVec3::new(x, -x - z, z)
+    }
+}
+
 impl HexagonCell2d {
+    /// Neighbor coordinate offsets, indexed as the
+    /// [`Cell::neighbor_coordinates`] neighbors
+    pub const NEIGHBOR_OFFSETS: [IVec3; 6] = NEIGHBOR_COORDINATES;
+
     /// Instantiates a new cell with `coords` values
     #[must_use]
     #[inline]
@@ -1,20 +1,30 @@
 use bevy::prelude::Component;
+#[cfg(feature = "1D")]
+pub use cell_1d::*;
+pub use neighborhood::*;
 use std::{fmt::Debug, hash::Hash};
+pub use toroidal_cell::*;
 #[cfg(feature = "2D")]
-pub use {hexagon_2d_cell::*, moore_2d_cell::*, neumann_2d_cell::*};
+pub use {hexagon_2d_cell::*, moore_2d_cell::*, neumann_2d_cell::*, ranged_2d_cell::*};
 #[cfg(feature = "3D")]
 pub use {moore_3d_cell::*, neumann_3d_cell::*};
 
+#[cfg(feature = "1D")]
+mod cell_1d;
 #[cfg(feature = "2D")]
 mod hexagon_2d_cell;
 #[cfg(feature = "2D")]
 mod moore_2d_cell;
 #[cfg(feature = "3D")]
 mod moore_3d_cell;
+mod neighborhood;
 #[cfg(feature = "2D")]
 mod neumann_2d_cell;
 #[cfg(feature = "3D")]
 mod neumann_3d_cell;
+#[cfg(feature = "2D")]
+mod ranged_2d_cell;
+mod toroidal_cell;
 
 /// Trait defining a Cell, every cell type (2d, 3d, hexagonal, etc) must
 /// implement this trait and define an associated `Coordinates` type
@@ -26,7 +36,85 @@ pub trait Cell: Clone + Component {
     #[must_use]
     fn coords(&self) -> &Self::Coordinates;
 
-    /// Retrieves the coordinates of the neighbor cells
+    /// Retrieves the coordinates of the neighbor cells.
+    ///
+    /// The neighbors are always retrieved in the same order, so the index of a
+    /// neighbor identifies its direction. These are the indices given to
+    /// [`CellState::new_cell_state_positional`](crate::CellState::new_cell_state_positional),
+    /// the provided cells expose the matching coordinate offsets as a
+    /// `NEIGHBOR_OFFSETS` constant, or a `neighbor_offsets` function for ranged
+    /// cells.
     #[must_use]
     fn neighbor_coordinates(&self) -> impl ExactSizeIterator<Item = Self::Coordinates> + '_;
+
+    /// Retrieves the weights of the neighbor cells, in the same order as
+    /// [`Self::neighbor_coordinates`], for cells with weighted neighborhoods
+    /// (kernels).
+    ///
+    /// The weights can't depend on the cell coordinates, they are computed once
+    /// per simulation and given to the rules through
+    /// [`CellContext::neighbor_weights`](crate::CellContext::neighbor_weights).
+    /// By default every neighbor has a `1.0` weight and `None` is returned.
+    #[must_use]
+    #[inline]
+    fn neighbor_weights() -> Option<Vec<f32>> {
+        None
+    }
+
+    /// Retrieves the shape and range of the neighborhood of the cell type, for
+    /// rules defining their own neighborhood like the
+    /// [`LargerThanLifeRule`](crate::LargerThanLifeRule).
+    ///
+    /// By default the neighborhood isn't a [`NeighborhoodShape`] and `None` is
+    /// returned.
+    #[must_use]
+    #[inline]
+    fn neighborhood() -> Option<(NeighborhoodShape, i32)> {
+        None
+    }
+
+    /// Retrieves the coordinates mirroring the missing `neighbor` of the cell
+    /// through the world edges, for the
+    /// [`Boundary::Mirror`](crate::Boundary::Mirror) condition. `contains`
+    /// checks if coordinates match a cell of the world.
+    ///
+    /// By default the neighbor is not mirrored and `None` is returned.
+    #[must_use]
+    #[inline]
+    fn mirrored_coordinates(
+        &self,
+        neighbor: &Self::Coordinates,
+        contains: impl Fn(&Self::Coordinates) -> bool,
+    ) -> Option<Self::Coordinates> {
+        let _ = (neighbor, contains);
+        None
+    }
+}
+
+/// Mirrors the `neighbor` coordinates of the `cell` through the world edges on
+/// every axis. The edge of an axis is the last coordinate matching a cell, from
+/// the `cell` towards the `neighbor`, and is not duplicated by the mirror.
+///
+/// # Returns
+///
+/// The mirrored coordinates, if they differ from `neighbor` and match a cell
+#[cfg(any(feature = "1D", feature = "2D", feature = "3D"))]
+fn mirror_coordinates<const N: usize>(
+    cell: [i32; N],
+    neighbor: [i32; N],
+    contains: impl Fn([i32; N]) -> bool,
+) -> Option<[i32; N]> {
+    let mut image = neighbor;
+    for axis in 0..N {
+        let step = (neighbor[axis] - cell[axis]).signum();
+        let mut probe = cell;
+        while probe[axis] != neighbor[axis] {
+            probe[axis] += step;
+            if !contains(probe) {
+                image[axis] = 2 * (probe[axis] - step) - neighbor[axis];
+                break;
+            }
+        }
+    }
+    (image != neighbor && contains(image)).then_some(image)
 }
@@ -1,4 +1,5 @@
-use crate::components::Cell;
+use super::mirror_coordinates;
+use crate::components::{Cell, NeighborhoodShape, WrappingCell};
 use bevy::prelude::{Component, IVec2};
 use std::ops::Deref;
 
@@ -67,9 +68,39 @@ impl Cell for MooreCell2d {
     fn neighbor_coordinates(&self) -> impl ExactSizeIterator<Item = Self::Coordinates> + '_ {
         NEIGHBOR_COORDINATES.map(|c| c + *self.coords()).into_iter()
     }
+
+    #[inline]
+    fn neighborhood() -> Option<(NeighborhoodShape, i32)> {
+        Some((NeighborhoodShape::Moore, 1))
+    }
+
+    #[inline]
+    fn mirrored_coordinates(
+        &self,
+        neighbor: &Self::Coordinates,
+        contains: impl Fn(&Self::Coordinates) -> bool,
+    ) -> Option<Self::Coordinates> {
+        mirror_coordinates(self.coords.to_array(), neighbor.to_array(), |c| {
+            contains(&IVec2::from_array(c))
+        })
+        .map(IVec2::from_array)
+    }
+}
+
+impl WrappingCell for MooreCell2d {
+    type Dimensions = IVec2;
+
+    #[inline]
+    fn wrap(coordinates: Self::Coordinates, dimensions: &Self::Dimensions) -> Self::Coordinates {
+        coordinates.rem_euclid(*dimensions)
+    }
 }
 
 impl MooreCell2d {
+    /// Neighbor coordinate offsets, indexed as the
+    /// [`Cell::neighbor_coordinates`] neighbors
+    pub const NEIGHBOR_OFFSETS: [IVec2; 8] = NEIGHBOR_COORDINATES;
+
     /// Instantiates a new cell with `coords` values
     #[must_use]
     #[inline]
@@ -168,4 +199,38 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn mirrored_coordinates() {
+        let contains = |c: &IVec2| (0..3).contains(&c.x) && (0..3).contains(&c.y);
+        let corner = MooreCell2d::new(IVec2::ZERO);
+        let mirrored: Vec<_> = corner
+            .neighbor_coordinates()
+            .filter(|c| !contains(c))
+            .map(|c| corner.mirrored_coordinates(&c, contains))
+            .collect();
+        assert_eq!(
+            mirrored,
+            [
+                // Left, Top Left
+                Some(IVec2::new(1, 0)),
+                Some(IVec2::new(1, 1)),
+                // Bottom Right, Bottom, Bottom Left
+                Some(IVec2::new(1, 1)),
+                Some(IVec2::new(0, 1)),
+                Some(IVec2::new(1, 1)),
+            ]
+        );
+        let edge = MooreCell2d::new(IVec2::new(0, 1));
+        assert_eq!(
+            edge.mirrored_coordinates(&IVec2::new(-1, 2), contains),
+            Some(IVec2::new(1, 2))
+        );
+        // Holes are not mirrored
+        let center = MooreCell2d::new(IVec2::ONE);
+        assert_eq!(
+            center.mirrored_coordinates(&IVec2::new(2, 2), |c| *c != IVec2::new(2, 2)),
+            None
+        );
+    }
 }
@@ -0,0 +1,251 @@
+use bevy::math::IVec2;
+
+/// Shape of a ranged cell neighborhood, made of every cell within a range
+/// according to a distance metric
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum NeighborhoodShape {
+    /// Square neighborhood (Chebyshev distance)
+    #[default]
+    Moore,
+    /// Diamond neighborhood (Manhattan distance)
+    VonNeumann,
+    /// Disk neighborhood (Euclidean distance)
+    Circular,
+    /// Cross neighborhood, only the cells aligned with the center cell
+    Cross,
+}
+
+impl NeighborhoodShape {
+    /// Half width of the neighborhood row at the vertical offset `dy`, for the
+    /// given `range`
+    #[must_use]
+    #[inline]
+    pub const fn half_width(self, range: i32, dy: i32) -> i32 {
+        match self {
+            Self::Moore => range,
+            Self::VonNeumann => range - dy.abs(),
+            Self::Circular => (range * range - dy * dy).isqrt(),
+            Self::Cross if dy == 0 => range,
+            Self::Cross => 0,
+        }
+    }
+
+    /// Amount of neighbors in the neighborhood for the given `range`,
+    /// excluding the center cell
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub const fn neighbor_count(self, range: i32) -> usize {
+        let mut count = 0;
+        let mut dy = -range;
+        while dy <= range {
+            count += 2 * self.half_width(range, dy) as usize + 1;
+            dy += 1;
+        }
+        count.saturating_sub(1)
+    }
+
+    /// Iterates over the neighbor offsets for the given `range`, excluding the
+    /// center cell, row by row from bottom left to top right
+    #[must_use]
+    #[inline]
+    pub const fn offsets(self, range: i32) -> NeighborhoodOffsets {
+        NeighborhoodOffsets {
+            range,
+            shape: self,
+            offset: IVec2::new(-self.half_width(range, -range), -range),
+            remaining: self.neighbor_count(range),
+        }
+    }
+}
+
+/// Iterator over the neighbor offsets of a [`NeighborhoodShape`], see
+/// [`NeighborhoodShape::offsets`]
+#[derive(Debug, Clone)]
+pub struct NeighborhoodOffsets {
+    range: i32,
+    shape: NeighborhoodShape,
+    offset: IVec2,
+    remaining: usize,
+}
+
+impl Iterator for NeighborhoodOffsets {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            if self.offset.x > self.shape.half_width(self.range, self.offset.y) {
+                self.offset.y += 1;
+                self.offset.x = -self.shape.half_width(self.range, self.offset.y);
+                continue;
+            }
+            let offset = self.offset;
+            self.offset.x += 1;
+            if offset != IVec2::ZERO {
+                self.remaining -= 1;
+                return Some(offset);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for NeighborhoodOffsets {}
+
+/// Trait for the neighborhood marker types of ranged cells
+pub trait Neighborhood: Send + Sync + 'static {
+    /// The neighborhood shape
+    const SHAPE: NeighborhoodShape;
+
+    /// Weight of the neighbor at `offset` from the center cell, for the given
+    /// `range`. Every neighbor has a `1.0` weight by default.
+    #[must_use]
+    #[inline]
+    fn weight(offset: IVec2, range: i32) -> f32 {
+        let _ = (offset, range);
+        1.0
+    }
+
+    /// Weights of every neighbor for the given `range`, in the
+    /// [`NeighborhoodShape::offsets`] order, computed from [`Self::weight`]
+    #[must_use]
+    fn weights(range: i32) -> Vec<f32>
+    where
+        Self: Sized,
+    {
+        Self::SHAPE
+            .offsets(range)
+            .map(|offset| Self::weight(offset, range))
+            .collect()
+    }
+}
+
+/// Square [`Neighborhood`] marker (see [`NeighborhoodShape::Moore`])
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct MooreNeighborhood;
+
+/// Diamond [`Neighborhood`] marker (see [`NeighborhoodShape::VonNeumann`])
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct NeumannNeighborhood;
+
+/// Disk [`Neighborhood`] marker (see [`NeighborhoodShape::Circular`])
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct CircularNeighborhood;
+
+/// Cross [`Neighborhood`] marker (see [`NeighborhoodShape::Cross`])
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct CrossNeighborhood;
+
+/// Disk [`Neighborhood`] marker with gaussian weights, decreasing with the
+/// distance to the center cell, with a standard deviation of half the range
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct GaussianNeighborhood;
+
+/// Disk [`Neighborhood`] marker with ring weights, a smooth bump peaking at
+/// half the range and vanishing at the center and the edge, like a Lenia
+/// kernel
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct RingNeighborhood;
+
+impl Neighborhood for MooreNeighborhood {
+    const SHAPE: NeighborhoodShape = NeighborhoodShape::Moore;
+}
+
+impl Neighborhood for NeumannNeighborhood {
+    const SHAPE: NeighborhoodShape = NeighborhoodShape::VonNeumann;
+}
+
+impl Neighborhood for CircularNeighborhood {
+    const SHAPE: NeighborhoodShape = NeighborhoodShape::Circular;
+}
+
+impl Neighborhood for CrossNeighborhood {
+    const SHAPE: NeighborhoodShape = NeighborhoodShape::Cross;
+}
+
+impl Neighborhood for GaussianNeighborhood {
+    const SHAPE: NeighborhoodShape = NeighborhoodShape::Circular;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn weight(offset: IVec2, range: i32) -> f32 {
+        let sigma = range as f32 / 2.0;
+        let distance_squared = offset.length_squared() as f32;
+        (-distance_squared / (2.0 * sigma * sigma)).exp()
+    }
+}
+
+impl Neighborhood for RingNeighborhood {
+    const SHAPE: NeighborhoodShape = NeighborhoodShape::Circular;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn weight(offset: IVec2, range: i32) -> f32 {
+        Self::bump(offset.as_vec2().length() / range as f32)
+    }
+}
+
+impl RingNeighborhood {
+    /// Smooth bump function of the ring weights, peaking at `1.0` for
+    /// `x = 0.5` and vanishing outside of `0.0..1.0`. It is also the ring
+    /// profile of a [`LeniaKernel`](crate::LeniaKernel)
+    #[must_use]
+    pub fn bump(x: f32) -> f32 {
+        if x <= 0.0 || x >= 1.0 {
+            return 0.0;
+        }
+        (4.0 - 1.0 / (x * (1.0 - x))).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbor_counts() {
+        assert_eq!(NeighborhoodShape::Moore.neighbor_count(1), 8);
+        assert_eq!(NeighborhoodShape::VonNeumann.neighbor_count(1), 4);
+        assert_eq!(NeighborhoodShape::Circular.neighbor_count(1), 4);
+        assert_eq!(NeighborhoodShape::Cross.neighbor_count(1), 4);
+        assert_eq!(NeighborhoodShape::Moore.neighbor_count(5), 120);
+        assert_eq!(NeighborhoodShape::VonNeumann.neighbor_count(2), 12);
+        assert_eq!(NeighborhoodShape::Circular.neighbor_count(2), 12);
+        assert_eq!(NeighborhoodShape::Cross.neighbor_count(2), 8);
+        assert_eq!(NeighborhoodShape::Moore.neighbor_count(0), 0);
+    }
+
+    #[test]
+    fn neighbor_weights() {
+        assert!((MooreNeighborhood::weight(IVec2::new(3, 3), 3) - 1.0).abs() < f32::EPSILON);
+        let gaussian = |x, y| GaussianNeighborhood::weight(IVec2::new(x, y), 4);
+        assert!((gaussian(2, 0) - (-0.5f32).exp()).abs() < f32::EPSILON);
+        assert!(gaussian(1, 0) > gaussian(1, 1));
+        assert!(gaussian(1, 1) > gaussian(0, 4));
+        let ring = |x, y| RingNeighborhood::weight(IVec2::new(x, y), 4);
+        assert!((ring(2, 0) - 1.0).abs() < f32::EPSILON);
+        assert!(ring(0, 4).abs() < f32::EPSILON);
+        assert!(ring(1, 0) < ring(0, 2));
+        assert!(ring(3, 0) < ring(0, 2));
+    }
+
+    #[test]
+    fn weight_tables() {
+        let weights = RingNeighborhood::weights(4);
+        assert_eq!(weights.len(), NeighborhoodShape::Circular.neighbor_count(4));
+        for (weight, offset) in weights.iter().zip(NeighborhoodShape::Circular.offsets(4)) {
+            assert!((weight - RingNeighborhood::weight(offset, 4)).abs() < f32::EPSILON);
+        }
+        assert_eq!(RingNeighborhood::weights(3).len(), 28);
+        let gaussian = GaussianNeighborhood::weights(4);
+        assert!(gaussian.iter().all(|&weight| weight > 0.0));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::components::Cell;
+use super::mirror_coordinates;
+use crate::components::{Cell, NeighborhoodShape, WrappingCell};
 use bevy::prelude::{Component, IVec2};
 use std::ops::Deref;
 
@@ -58,9 +59,39 @@ impl Cell for NeumannCell2d {
     fn neighbor_coordinates(&self) -> impl ExactSizeIterator<Item = Self::Coordinates> + '_ {
         NEIGHBOR_COORDINATES.map(|c| c + *self.coords()).into_iter()
     }
+
+    #[inline]
+    fn neighborhood() -> Option<(NeighborhoodShape, i32)> {
+        Some((NeighborhoodShape::VonNeumann, 1))
+    }
+
+    #[inline]
+    fn mirrored_coordinates(
+        &self,
+        neighbor: &Self::Coordinates,
+        contains: impl Fn(&Self::Coordinates) -> bool,
+    ) -> Option<Self::Coordinates> {
+        mirror_coordinates(self.coords.to_array(), neighbor.to_array(), |c| {
+            contains(&IVec2::from_array(c))
+        })
+        .map(IVec2::from_array)
+    }
+}
+
+impl WrappingCell for NeumannCell2d {
+    type Dimensions = IVec2;
+
+    #[inline]
+    fn wrap(coordinates: Self::Coordinates, dimensions: &Self::Dimensions) -> Self::Coordinates {
+        coordinates.rem_euclid(*dimensions)
+    }
 }
 
 impl NeumannCell2d {
+    /// Neighbor coordinate offsets, indexed as the
+    /// [`Cell::neighbor_coordinates`] neighbors
+    pub const NEIGHBOR_OFFSETS: [IVec2; 4] = NEIGHBOR_COORDINATES;
+
     /// Instantiates a new cell with `coords` values
     #[must_use]
     #[inline]
@@ -1,4 +1,5 @@
-use crate::components::Cell;
+use super::mirror_coordinates;
+use crate::components::{Cell, WrappingCell};
 use bevy::prelude::{Component, IVec3};
 use std::ops::Deref;
 
@@ -54,9 +55,34 @@ impl Cell for NeumannCell3d {
     fn neighbor_coordinates(&self) -> impl ExactSizeIterator<Item = Self::Coordinates> + '_ {
         NEIGHBOR_COORDINATES.map(|c| c + *self.coords()).into_iter()
     }
+
+    #[inline]
+    fn mirrored_coordinates(
+        &self,
+        neighbor: &Self::Coordinates,
+        contains: impl Fn(&Self::Coordinates) -> bool,
+    ) -> Option<Self::Coordinates> {
+        mirror_coordinates(self.coords.to_array(), neighbor.to_array(), |c| {
+            contains(&IVec3::from_array(c))
+        })
+        .map(IVec3::from_array)
+    }
+}
+
+impl WrappingCell for NeumannCell3d {
+    type Dimensions = IVec3;
+
+    #[inline]
+    fn wrap(coordinates: Self::Coordinates, dimensions: &Self::Dimensions) -> Self::Coordinates {
+        coordinates.rem_euclid(*dimensions)
+    }
 }
 
 impl NeumannCell3d {
+    /// Neighbor coordinate offsets, indexed as the
+    /// [`Cell::neighbor_coordinates`] neighbors
+    pub const NEIGHBOR_OFFSETS: [IVec3; 6] = NEIGHBOR_COORDINATES;
+
     /// Instantiates a new cell with `coords` values
     #[must_use]
     #[inline]
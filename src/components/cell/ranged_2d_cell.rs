@@ -0,0 +1,210 @@
+use super::mirror_coordinates;
+use crate::components::{
+    Cell, CircularNeighborhood, CrossNeighborhood, GaussianNeighborhood, LeniaNeighborhood,
+    MooreNeighborhood, Neighborhood, NeighborhoodOffsets, NeighborhoodShape, NeumannNeighborhood,
+    RingNeighborhood, SmoothLifeNeighborhood, WrappingCell,
+};
+use bevy::prelude::{Component, IVec2};
+use std::{marker::PhantomData, ops::Deref};
+
+/// Ranged 2D cell, its neighbors are every cell within the `R` range according
+/// to the `N` [`Neighborhood`] shape. It uses `IVec2` coordinates.
+///
+/// The neighbors are ordered row by row, from bottom left to top right, and
+/// weighted by the [`Neighborhood::weight`] kernel (see
+/// [`Neighborhood::weights`]).
+///
+/// ```ascii
+/// Moore (R = 2)   Von Neumann     Circular        Cross
+/// # # # # #           #             # # #             #
+/// # # # # #         # # #         # # # # #           #
+/// # # o # #       # # o # #       # # o # #       # # o # #
+/// # # # # #         # # #         # # # # #           #
+/// # # # # #           #             # # #             #
+/// ```
+///
+/// See the [`MooreRangeCell2d`], [`NeumannRangeCell2d`],
+/// [`CircularRangeCell2d`] and [`CrossRangeCell2d`] aliases, and the weighted
+/// [`GaussianRangeCell2d`], [`RingRangeCell2d`], [`LeniaRangeCell2d`] and
+/// [`SmoothLifeRangeCell2d`] aliases
+#[derive(Debug, Clone, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct RangedCell2d<N: Neighborhood, const R: i32> {
+    /// The 2D cell coordinates
+    pub coords: IVec2,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    phantom: PhantomData<N>,
+}
+
+/// Ranged 2D cell with a square neighborhood of range `R`
+pub type MooreRangeCell2d<const R: i32> = RangedCell2d<MooreNeighborhood, R>;
+/// Ranged 2D cell with a diamond neighborhood of range `R`
+pub type NeumannRangeCell2d<const R: i32> = RangedCell2d<NeumannNeighborhood, R>;
+/// Ranged 2D cell with a disk neighborhood of radius `R`
+pub type CircularRangeCell2d<const R: i32> = RangedCell2d<CircularNeighborhood, R>;
+/// Ranged 2D cell with a cross neighborhood of range `R`
+pub type CrossRangeCell2d<const R: i32> = RangedCell2d<CrossNeighborhood, R>;
+/// Ranged 2D cell with a disk neighborhood of radius `R` and gaussian weights
+pub type GaussianRangeCell2d<const R: i32> = RangedCell2d<GaussianNeighborhood, R>;
+/// Ranged 2D cell with a disk neighborhood of radius `R` and ring weights
+pub type RingRangeCell2d<const R: i32> = RangedCell2d<RingNeighborhood, R>;
+/// Ranged 2D cell weighted by the kernel of the `L` Lenia rule, the `R` range
+/// must match the kernel radius
+pub type LeniaRangeCell2d<L, const R: i32> = RangedCell2d<LeniaNeighborhood<L>, R>;
+/// Ranged 2D cell weighted by the inner disk of the `L` `SmoothLife` rule, the
+/// `R` range must match the outer radius
+pub type SmoothLifeRangeCell2d<L, const R: i32> = RangedCell2d<SmoothLifeNeighborhood<L>, R>;
+
+impl<N: Neighborhood, const R: i32> Deref for RangedCell2d<N, R> {
+    type Target = IVec2;
+
+    fn deref(&self) -> &Self::Target {
+        &self.coords
+    }
+}
+
+impl<N: Neighborhood + Clone, const R: i32> Cell for RangedCell2d<N, R> {
+    type Coordinates = IVec2;
+
+    #[inline]
+    fn coords(&self) -> &Self::Coordinates {
+        &self.coords
+    }
+
+    #[inline]
+    fn neighbor_coordinates(&self) -> impl ExactSizeIterator<Item = Self::Coordinates> + '_ {
+        Self::neighbor_offsets().map(move |offset| self.coords + offset)
+    }
+
+    #[inline]
+    fn neighbor_weights() -> Option<Vec<f32>> {
+        Some(N::weights(R))
+    }
+
+    #[inline]
+    fn neighborhood() -> Option<(NeighborhoodShape, i32)> {
+        Some((N::SHAPE, R))
+    }
+
+    #[inline]
+    fn mirrored_coordinates(
+        &self,
+        neighbor: &Self::Coordinates,
+        contains: impl Fn(&Self::Coordinates) -> bool,
+    ) -> Option<Self::Coordinates> {
+        mirror_coordinates(self.coords.to_array(), neighbor.to_array(), |c| {
+            contains(&IVec2::from_array(c))
+        })
+        .map(IVec2::from_array)
+    }
+}
+
+impl<N: Neighborhood + Clone, const R: i32> WrappingCell for RangedCell2d<N, R> {
+    type Dimensions = IVec2;
+
+    #[inline]
+    fn wrap(coordinates: Self::Coordinates, dimensions: &Self::Dimensions) -> Self::Coordinates {
+        coordinates.rem_euclid(*dimensions)
+    }
+}
+
+impl<N: Neighborhood, const R: i32> RangedCell2d<N, R> {
+    /// Neighbor coordinate offsets, indexed as the
+    /// [`Cell::neighbor_coordinates`] neighbors
+    #[must_use]
+    #[inline]
+    pub const fn neighbor_offsets() -> NeighborhoodOffsets {
+        N::SHAPE.offsets(R)
+    }
+
+    /// Instantiates a new cell with `coords` values
+    #[must_use]
+    #[inline]
+    pub const fn new(coords: IVec2) -> Self {
+        Self {
+            coords,
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MooreCell2d;
+    use bevy::platform::collections::HashSet;
+
+    fn check_neighbors<N: Neighborhood + Clone, const R: i32>(distance: impl Fn(IVec2) -> bool) {
+        let center = IVec2::new(10, -10);
+        let cell = RangedCell2d::<N, R>::new(center);
+        let neighbors = cell.neighbor_coordinates();
+        assert_eq!(neighbors.len(), N::SHAPE.neighbor_count(R));
+        let neighbors: Vec<_> = neighbors.collect();
+        assert_eq!(neighbors.len(), N::SHAPE.neighbor_count(R));
+        let expected: Vec<_> = (-R..=R)
+            .flat_map(|y| (-R..=R).map(move |x| IVec2::new(x, y)))
+            .filter(|&offset| offset != IVec2::ZERO && distance(offset))
+            .map(|offset| center + offset)
+            .collect();
+        assert_eq!(neighbors, expected);
+    }
+
+    #[test]
+    fn neighbor_shapes() {
+        check_neighbors::<MooreNeighborhood, 3>(|_| true);
+        check_neighbors::<NeumannNeighborhood, 3>(|o| o.x.abs() + o.y.abs() <= 3);
+        check_neighbors::<CircularNeighborhood, 3>(|o| o.length_squared() <= 9);
+        check_neighbors::<CrossNeighborhood, 3>(|o| o.x == 0 || o.y == 0);
+        check_neighbors::<MooreNeighborhood, 0>(|_| false);
+    }
+
+    #[test]
+    fn neighbor_weights() {
+        let cell = GaussianRangeCell2d::<3>::new(IVec2::new(3, 5));
+        let weights = GaussianRangeCell2d::<3>::neighbor_weights().unwrap();
+        assert_eq!(weights.len(), cell.neighbor_coordinates().len());
+        for (weight, neighbor) in weights.into_iter().zip(cell.neighbor_coordinates()) {
+            let expected = GaussianNeighborhood::weight(neighbor - cell.coords, 3);
+            assert!((weight - expected).abs() < f32::EPSILON);
+        }
+        let weights = CircularRangeCell2d::<3>::neighbor_weights().unwrap();
+        assert!(weights
+            .iter()
+            .all(|weight| (weight - 1.0).abs() < f32::EPSILON));
+    }
+
+    #[test]
+    fn matches_moore_cell() {
+        let cell = MooreRangeCell2d::<1>::new(IVec2::new(3, 5));
+        let moore = MooreCell2d::new(IVec2::new(3, 5));
+        let neighbors: HashSet<_> = cell.neighbor_coordinates().collect();
+        let expected: HashSet<_> = moore.neighbor_coordinates().collect();
+        assert_eq!(neighbors, expected);
+    }
+
+    #[test]
+    fn neighbor_offsets() {
+        let cell = CircularRangeCell2d::<3>::new(IVec2::new(-4, 7));
+        let offsets: Vec<_> = cell
+            .neighbor_coordinates()
+            .map(|c| c - cell.coords)
+            .collect();
+        let expected: Vec<_> = CircularRangeCell2d::<3>::neighbor_offsets().collect();
+        assert_eq!(offsets, expected);
+    }
+
+    #[test]
+    fn mirrored_coordinates() {
+        let contains = |c: &IVec2| (0..10).contains(&c.x) && (0..10).contains(&c.y);
+        let cell = MooreRangeCell2d::<3>::new(IVec2::new(1, 5));
+        // Mirrored through the `x = 0` edge, not through the cell
+        assert_eq!(
+            cell.mirrored_coordinates(&IVec2::new(-2, 7), contains),
+            Some(IVec2::new(2, 7))
+        );
+        assert_eq!(
+            cell.mirrored_coordinates(&IVec2::new(-1, 5), contains),
+            Some(IVec2::new(1, 5))
+        );
+    }
+}
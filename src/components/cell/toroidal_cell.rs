@@ -0,0 +1,164 @@
+use crate::components::{Cell, NeighborhoodShape};
+use bevy::prelude::Component;
+use std::{fmt::Debug, ops::Deref};
+
+/// Trait for cells whose coordinates can be wrapped around a finite world,
+/// allowing them to be used in a [`ToroidalCell`]
+pub trait WrappingCell: Cell {
+    /// Associated world dimensions type
+    type Dimensions: Clone + Debug + Send + Sync + 'static;
+
+    /// Wraps `coordinates` around a world of the given `dimensions`, starting
+    /// at the origin
+    #[must_use]
+    fn wrap(coordinates: Self::Coordinates, dimensions: &Self::Dimensions) -> Self::Coordinates;
+}
+
+/// Toroidal (wrap-around) cell, wrapping the neighbor coordinates of the
+/// `cell` around a world of fixed `dimensions` so patterns leaving an edge
+/// reappear on the opposite one.
+///
+/// # Note
+///
+/// The `cell` coordinates must be in the world bounds, from the origin to
+/// `dimensions` (excluded).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # #[cfg(feature = "2D")]
+/// # use bevy_life::{CellularAutomatonPlugin, ConwayCellState, MooreCell2d, ToroidalCell};
+/// # #[cfg(feature = "2D")]
+/// type ToroidalGameOfLife = CellularAutomatonPlugin<ToroidalCell<MooreCell2d>, ConwayCellState>;
+///
+/// # #[cfg(feature = "2D")]
+/// fn spawn_cell(mut commands: Commands) {
+///     let cell = MooreCell2d::new(IVec2::new(0, 0));
+///     commands.spawn((
+///         ToroidalCell::new(cell, IVec2::new(100, 100)),
+///         ConwayCellState(true),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct ToroidalCell<C: WrappingCell> {
+    /// The wrapped cell
+    pub cell: C,
+    /// The world dimensions
+    pub dimensions: C::Dimensions,
+}
+
+impl<C: WrappingCell> Deref for ToroidalCell<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cell
+    }
+}
+
+impl<C: WrappingCell> Cell for ToroidalCell<C> {
+    type Coordinates = C::Coordinates;
+
+    #[inline]
+    fn coords(&self) -> &Self::Coordinates {
+        self.cell.coords()
+    }
+
+    #[inline]
+    fn neighbor_coordinates(&self) -> impl ExactSizeIterator<Item = Self::Coordinates> + '_ {
+        self.cell
+            .neighbor_coordinates()
+            .map(|c| C::wrap(c, &self.dimensions))
+    }
+
+    #[inline]
+    fn neighbor_weights() -> Option<Vec<f32>> {
+        C::neighbor_weights()
+    }
+
+    #[inline]
+    fn neighborhood() -> Option<(NeighborhoodShape, i32)> {
+        C::neighborhood()
+    }
+}
+
+impl<C: WrappingCell> ToroidalCell<C> {
+    /// Instantiates a new toroidal cell wrapping `cell` around a world of the
+    /// given `dimensions`
+    #[must_use]
+    #[inline]
+    pub const fn new(cell: C, dimensions: C::Dimensions) -> Self {
+        Self { cell, dimensions }
+    }
+}
+
+#[cfg(all(test, feature = "2D"))]
+mod tests {
+    use super::*;
+    use crate::{HexagonCell2d, MooreCell2d, NeumannCell2d};
+    use bevy::prelude::{IVec2, IVec3};
+
+    #[test]
+    fn moore_corner() {
+        let cell = ToroidalCell::new(MooreCell2d::new(IVec2::ZERO), IVec2::new(10, 5));
+        let neighbors = cell.neighbor_coordinates().collect::<Vec<_>>();
+        assert_eq!(
+            neighbors,
+            vec![
+                // Left
+                IVec2::new(9, 0),
+                // Top Left
+                IVec2::new(9, 1),
+                // Top
+                IVec2::new(0, 1),
+                // Top Right
+                IVec2::new(1, 1),
+                // Right
+                IVec2::new(1, 0),
+                // Bottom Right
+                IVec2::new(1, 4),
+                // Bottom
+                IVec2::new(0, 4),
+                // Bottom Left
+                IVec2::new(9, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn neumann_corner() {
+        let cell = ToroidalCell::new(NeumannCell2d::new(IVec2::new(9, 4)), IVec2::new(10, 5));
+        let neighbors = cell.neighbor_coordinates().collect::<Vec<_>>();
+        assert_eq!(
+            neighbors,
+            vec![
+                IVec2::new(8, 4),
+                IVec2::new(9, 0),
+                IVec2::new(0, 4),
+                IVec2::new(9, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn hexagon_corner() {
+        let cell = ToroidalCell::new(HexagonCell2d::new(IVec3::ZERO), IVec2::new(10, 5));
+        let neighbors = cell.neighbor_coordinates().collect::<Vec<_>>();
+        assert_eq!(
+            neighbors,
+            vec![
+                IVec3::new(0, -4, 4),
+                IVec3::new(1, -5, 4),
+                IVec3::new(1, -1, 0),
+                IVec3::new(0, -1, 1),
+                IVec3::new(9, -10, 1),
+                IVec3::new(9, -9, 0),
+            ]
+        );
+        for neighbor in neighbors {
+            assert_eq!(neighbor.x + neighbor.y + neighbor.z, 0);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::components::{CellState, ConwayCellState};
+use bevy::math::IVec2;
+
+/// Offsets of the cells of a 2x2 Margolus block from the block origin, the
+/// bottom left cell.
+///
+/// This is the order of the block states given to and returned by
+/// [`BlockRule::new_block_state`]: bottom left, bottom right, top left, top
+/// right.
+pub const BLOCK_OFFSETS: [IVec2; 4] = [
+    IVec2::new(0, 0),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+
+/// Block cellular automaton rule, updating 2x2 blocks of cells at once
+/// instead of single cells, to use with the
+/// [`BlockCellularAutomatonPlugin`](crate::BlockCellularAutomatonPlugin).
+///
+/// The grid is partitioned in blocks following the Margolus neighborhood: on
+/// even generations blocks start on even coordinates, on odd generations the
+/// partition is shifted by one cell on both axes.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{BlockRule, ConwayCellState};
+/// /// Every block rotates a quarter turn counterclockwise
+/// struct RotationRule;
+///
+/// impl BlockRule for RotationRule {
+///     type State = ConwayCellState;
+///
+///     fn new_block_state(block: [Self::State; 4], _generation: u64) -> [Self::State; 4] {
+///         let [bottom_left, bottom_right, top_left, top_right] = block;
+///         [bottom_right, top_right, bottom_left, top_left]
+///     }
+/// }
+/// ```
+pub trait BlockRule: Send + Sync + 'static {
+    /// The cell state type. Its [`CellState::new_cell_state`] rule is not used,
+    /// missing cells of a block use the default state.
+    type State: CellState + Default;
+
+    /// Does [`Self::new_block_state`] only depend on the block states and the
+    /// parity of the generation, meaning that a block may only change if one
+    /// of its cells changed since the block was last evaluated, two
+    /// generations before.
+    ///
+    /// If `true` (default), only such *active* blocks are evaluated every
+    /// generation, stable areas are never recomputed.
+    /// Set it to `false` for rules depending on the generation beyond its
+    /// parity, every block will then be evaluated every generation.
+    const CHANGE_DRIVEN: bool = true;
+
+    /// Computes the new states of a `block`, ordered as [`BLOCK_OFFSETS`], for
+    /// the given `generation`.
+    ///
+    /// The states of missing cells are ignored.
+    #[must_use]
+    fn new_block_state(block: [Self::State; 4], generation: u64) -> [Self::State; 4];
+}
+
+/// Critters reversible block rule, on binary [`ConwayCellState`] cells:
+///
+/// - Blocks with exactly two live cells are unchanged.
+/// - Other blocks are inverted.
+/// - Blocks with three live cells are also rotated by a half turn.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CrittersRule;
+
+impl BlockRule for CrittersRule {
+    type State = ConwayCellState;
+
+    fn new_block_state(block: [Self::State; 4], _generation: u64) -> [Self::State; 4] {
+        match block.iter().filter(|state| state.0).count() {
+            2 => block,
+            3 => [3, 2, 1, 0].map(|i| ConwayCellState(!block[i].0)),
+            _ => block.map(|state| ConwayCellState(!state.0)),
+        }
+    }
+}
+
+/// Tron block rule, on binary [`ConwayCellState`] cells:
+///
+/// - Blocks where every cell has the same state are inverted.
+/// - Other blocks are unchanged.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TronRule;
+
+impl BlockRule for TronRule {
+    type State = ConwayCellState;
+
+    fn new_block_state(block: [Self::State; 4], _generation: u64) -> [Self::State; 4] {
+        if block.iter().all(|state| *state == block[0]) {
+            block.map(|state| ConwayCellState(!state.0))
+        } else {
+            block
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(states: [bool; 4]) -> [ConwayCellState; 4] {
+        states.map(ConwayCellState)
+    }
+
+    #[test]
+    fn critters() {
+        let two = block([true, false, false, true]);
+        assert_eq!(CrittersRule::new_block_state(two, 0), two);
+        let one = block([false, false, true, false]);
+        assert_eq!(
+            CrittersRule::new_block_state(one, 0),
+            block([true, true, false, true])
+        );
+        let three = block([true, true, true, false]);
+        assert_eq!(
+            CrittersRule::new_block_state(three, 0),
+            block([true, false, false, false])
+        );
+    }
+
+    #[test]
+    fn tron() {
+        let empty = block([false; 4]);
+        assert_eq!(TronRule::new_block_state(empty, 0), block([true; 4]));
+        let mixed = block([true, false, true, true]);
+        assert_eq!(TronRule::new_block_state(mixed, 1), mixed);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::components::{Cell, CellRng};
+use std::time::Duration;
+
+/// Context of a cell evaluation, given to
+/// [`CellState::new_cell_state_with_context`](crate::CellState::new_cell_state_with_context)
+///
+/// The context records whether the generation, the delta time or the random
+/// stream were read: the cell state then depends on time and not only on its
+/// neighbors, so the simulation keeps evaluating the cell every generation
+/// even if its neighborhood is stable.
+#[derive(Debug)]
+pub struct CellContext<'a, C> {
+    cell: &'a C,
+    generation: u64,
+    delta: Duration,
+    seed: u64,
+    neighbor_weights: Option<&'a [f32]>,
+    time_dependent: std::cell::Cell<bool>,
+}
+
+impl<'a, C: Cell> CellContext<'a, C> {
+    /// Instantiates a new context for `cell`
+    #[must_use]
+    #[inline]
+    pub const fn new(cell: &'a C, generation: u64, delta: Duration) -> Self {
+        Self {
+            cell,
+            generation,
+            delta,
+            seed: 0,
+            neighbor_weights: None,
+            time_dependent: std::cell::Cell::new(false),
+        }
+    }
+
+    /// Sets the [`SimulationRng`](crate::SimulationRng) seed used by
+    /// [`Self::rng`]
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the neighbor weights of the cell, see [`Cell::neighbor_weights`]
+    #[must_use]
+    #[inline]
+    pub const fn with_neighbor_weights(mut self, weights: Option<&'a [f32]>) -> Self {
+        self.neighbor_weights = weights;
+        self
+    }
+
+    /// Retrieves the evaluated cell
+    #[must_use]
+    #[inline]
+    pub const fn cell(&self) -> &'a C {
+        self.cell
+    }
+
+    /// Retrieves the evaluated cell coordinates
+    #[must_use]
+    #[inline]
+    pub fn coords(&self) -> &'a C::Coordinates {
+        self.cell.coords()
+    }
+
+    /// Retrieves the weights of the evaluated cell neighbors, in the order
+    /// defined by [`Cell::neighbor_coordinates`], or `None` if every neighbor
+    /// has a `1.0` weight. See [`Cell::neighbor_weights`]
+    #[must_use]
+    #[inline]
+    pub const fn neighbor_weights(&self) -> Option<&'a [f32]> {
+        self.neighbor_weights
+    }
+
+    /// Retrieves the weight of the neighbor at `index`, in the order defined by
+    /// [`Cell::neighbor_coordinates`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the neighbor weights don't have a weight for every neighbor
+    #[must_use]
+    #[inline]
+    pub fn neighbor_weight(&self, index: usize) -> f32 {
+        self.neighbor_weights.map_or(1.0, |weights| weights[index])
+    }
+
+    /// Retrieves the generation of the current cell states, the computed state
+    /// belongs to the next generation
+    #[must_use]
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.time_dependent.set(true);
+        self.generation
+    }
+
+    /// Retrieves the simulated duration of a generation: the
+    /// [`SimulationSpeed`](crate::SimulationSpeed) interval, or the frame delta
+    /// time if the simulation runs every frame
+    #[must_use]
+    #[inline]
+    pub fn delta(&self) -> Duration {
+        self.time_dependent.set(true);
+        self.delta
+    }
+
+    /// Retrieves the random stream of the cell for the current generation,
+    /// derived from the [`SimulationRng`](crate::SimulationRng) seed, the cell
+    /// coordinates and the generation.
+    ///
+    /// Every call returns the same stream, store it to draw multiple values.
+    #[must_use]
+    pub fn rng(&self) -> CellRng {
+        self.time_dependent.set(true);
+        CellRng::from_cell(self.seed, self.coords(), self.generation)
+    }
+}
+
+impl<C> CellContext<'_, C> {
+    /// Were the generation, the delta time or the random stream read during
+    /// the evaluation
+    #[inline]
+    pub(crate) const fn is_time_dependent(&self) -> bool {
+        self.time_dependent.get()
+    }
+}
+
+impl<C> Clone for CellContext<'_, C> {
+    fn clone(&self) -> Self {
+        Self {
+            cell: self.cell,
+            generation: self.generation,
+            delta: self.delta,
+            seed: self.seed,
+            neighbor_weights: self.neighbor_weights,
+            time_dependent: self.time_dependent.clone(),
+        }
+    }
+}
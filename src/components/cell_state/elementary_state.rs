@@ -0,0 +1,223 @@
+use crate::components::CellState;
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+use std::ops::{Deref, DerefMut};
+
+/// [Elementary cellular automaton] state, following the Wolfram rule number
+/// `RULE`.
+///
+/// The new state of a cell is the bit of `RULE` at the index formed by the
+/// left neighbor, the cell and the right neighbor states, read as a 3 bit
+/// number: `111` gives the 7th bit and `000` the first one. Missing neighbors
+/// are dead.
+///
+/// This state must be used with a [`Cell1d<1>`](crate::Cell1d), a dead cell
+/// is `false`, a live cell is `true`
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::ElementaryCellState;
+/// type Rule184CellState = ElementaryCellState<184>;
+/// ```
+///
+/// [Elementary cellular automaton]: https://en.wikipedia.org/wiki/Elementary_cellular_automaton
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct ElementaryCellState<const RULE: u8>(pub bool);
+
+/// Rule 30 cell state, a chaotic rule
+pub type Rule30CellState = ElementaryCellState<30>;
+/// Rule 90 cell state, drawing a Sierpiński triangle
+pub type Rule90CellState = ElementaryCellState<90>;
+/// Rule 110 cell state, a Turing complete rule
+pub type Rule110CellState = ElementaryCellState<110>;
+
+impl<const RULE: u8> ElementaryCellState<RULE> {
+    /// Computes the new state for the `left` and `right` neighbor states
+    const fn next(self, left: bool, right: bool) -> Self {
+        let index = (left as u8) << 2 | (self.0 as u8) << 1 | right as u8;
+        Self((RULE >> index) & 1 == 1)
+    }
+}
+
+impl<const RULE: u8> CellState for ElementaryCellState<RULE> {
+    /// Neighbors are taken in order, the first being the left neighbor and the
+    /// second the right neighbor.
+    ///
+    /// Without their positions, a missing left neighbor can't be told apart
+    /// from a missing right neighbor: a lone right neighbor is read as the left
+    /// one. Simulations use [`Self::new_cell_state_positional`] instead, which
+    /// handles missing neighbors, this method is only exact for complete
+    /// neighborhoods.
+    fn new_cell_state<'a>(&self, mut neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        let left = neighbor_cells.next().is_some_and(|c| c.0);
+        let right = neighbor_cells.next().is_some_and(|c| c.0);
+        self.next(left, right)
+    }
+
+    fn new_cell_state_positional<'a>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+    ) -> Self {
+        let (mut left, mut right) = (false, false);
+        for (index, state) in neighbor_cells {
+            let alive = state.is_some_and(|c| c.0);
+            match index {
+                0 => left = alive,
+                1 => right = alive,
+                _ => {}
+            }
+        }
+        self.next(left, right)
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        if self.0 {
+            Some(Color::WHITE)
+        } else {
+            None
+        }
+    }
+}
+
+impl<const RULE: u8> Deref for ElementaryCellState<RULE> {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const RULE: u8> DerefMut for ElementaryCellState<RULE> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const RULE: u8> From<bool> for ElementaryCellState<RULE> {
+    fn from(val: bool) -> Self {
+        Self(val)
+    }
+}
+
+/// [Totalistic] cellular automaton state with `K` colors, following the
+/// Wolfram totalistic `CODE`.
+///
+/// The new state of a cell is the digit of `CODE`, written in base `K`, at the
+/// index given by the sum of the cell and neighbor states. Missing neighbors
+/// count as `0`. The neighborhood radius is defined by the cell, like a
+/// [`Cell1d<R>`](crate::Cell1d).
+///
+/// The inner value is the cell color, from `0` to `K - 1`.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{Cell1d, CellularAutomatonPlugin, TotalisticCellState};
+/// // 3 colors, radius 1, code 1599
+/// type Code1599Plugin = CellularAutomatonPlugin<Cell1d<1>, TotalisticCellState<3, 1599>>;
+/// ```
+///
+/// [Totalistic]: https://en.wikipedia.org/wiki/Cellular_automaton#Totalistic
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct TotalisticCellState<const K: u8, const CODE: u128>(pub u8);
+
+impl<const K: u8, const CODE: u128> TotalisticCellState<K, CODE> {
+    /// Digit of the code for the given `sum`
+    #[allow(clippy::cast_possible_truncation)]
+    fn digit(sum: u32) -> u8 {
+        let k = u128::from(K.max(2));
+        k.checked_pow(sum)
+            .map_or(0, |position| ((CODE / position) % k) as u8)
+    }
+}
+
+impl<const K: u8, const CODE: u128> CellState for TotalisticCellState<K, CODE> {
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        let sum = neighbor_cells.fold(u32::from(self.0), |sum, c| sum + u32::from(c.0));
+        Self(Self::digit(sum))
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        if self.0 == 0 {
+            return None;
+        }
+        let c = f32::from(self.0) / f32::from(K.max(2) - 1);
+        Some(Color::srgb(c, c, c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_30() {
+        let state = |left, center, right| {
+            let neighbors = [Rule30CellState::from(left), Rule30CellState::from(right)];
+            Rule30CellState::from(center)
+                .new_cell_state_positional(neighbors.iter().map(Some).enumerate())
+                .0
+        };
+        // 30 = 0b0001_1110
+        assert!(!state(true, true, true));
+        assert!(!state(true, true, false));
+        assert!(!state(true, false, true));
+        assert!(state(true, false, false));
+        assert!(state(false, true, true));
+        assert!(state(false, true, false));
+        assert!(state(false, false, true));
+        assert!(!state(false, false, false));
+    }
+
+    #[test]
+    fn missing_neighbors() {
+        let alive = Rule110CellState::from(true);
+        let neighbors = [(0, None), (1, Some(&alive))];
+        // 110 = 0b0110_1110, `011` gives a live cell
+        assert!(alive.new_cell_state_positional(neighbors.into_iter()).0);
+        let neighbors = [(0, Some(&alive)), (1, None)];
+        // `110` gives a live cell
+        assert!(alive.new_cell_state_positional(neighbors.into_iter()).0);
+        let neighbors = [(0, Some(&alive)), (1, Some(&alive))];
+        // `111` gives a dead cell
+        assert!(!alive.new_cell_state_positional(neighbors.into_iter()).0);
+    }
+
+    #[test]
+    fn unpositioned_neighbors() {
+        // Rule 2 only gives a live cell for `001`
+        type State = ElementaryCellState<2>;
+        let (dead, alive) = (State::from(false), State::from(true));
+        assert!(dead.new_cell_state([dead, alive].iter()).0);
+        assert!(
+            dead.new_cell_state_positional(std::iter::once((1, Some(&alive))))
+                .0
+        );
+        // Without positions, a lone right neighbor is read as the left one
+        assert!(!dead.new_cell_state(std::iter::once(&alive)).0);
+    }
+
+    #[test]
+    fn totalistic_code() {
+        type State = TotalisticCellState<3, 1599>;
+        // 1599 = 2012020 in base 3
+        let state = |[left, center, right]: [u8; 3]| {
+            let neighbors: [State; 2] = [left, right].map(TotalisticCellState);
+            let cell: State = TotalisticCellState(center);
+            cell.new_cell_state(neighbors.iter()).0
+        };
+        assert_eq!(state([0, 0, 0]), 0);
+        assert_eq!(state([1, 0, 0]), 2);
+        assert_eq!(state([1, 1, 0]), 0);
+        assert_eq!(state([1, 1, 1]), 2);
+        assert_eq!(state([2, 1, 1]), 1);
+        assert_eq!(state([2, 2, 1]), 0);
+        assert_eq!(state([2, 2, 2]), 2);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::components::{Cell, CellContext, CellState};
+#[cfg(feature = "auto-coloring")]
+use bevy::color::{
+    palettes::css::{FOREST_GREEN, ORANGE_RED},
+    Color,
+};
+use bevy::prelude::Component;
+
+/// Stage of a [`ForestFireCellState`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum ForestFireStage {
+    #[default]
+    /// Empty cell
+    Empty,
+    /// Tree cell
+    Tree,
+    /// Burning tree cell
+    Burning,
+}
+
+/// Drossel-Schwabl forest-fire model, a stochastic cellular automaton where
+/// trees grow on empty cells and fires spread through the forest.
+///
+/// The model has the following rules:
+///
+/// - A burning cell ([`ForestFireStage::Burning`]) becomes empty.
+/// - A tree ([`ForestFireStage::Tree`]) burns if at least one neighbor is
+///   burning, or is struck by lightning with a `LIGHTNING` probability.
+/// - An empty cell ([`ForestFireStage::Empty`]) grows a tree with a `GROWTH`
+///   probability.
+///
+/// The probabilities are given in millionths, see
+/// [`DefaultForestFireCellState`] for common values. The random values are
+/// drawn from [`CellContext::rng`], the simulation can be seeded with the
+/// [`SimulationRng`](crate::SimulationRng) resource.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::ForestFireCellState;
+/// // 5% growth and 0.1% lightning probabilities
+/// type FastForestFireCellState = ForestFireCellState<50_000, 1_000>;
+/// assert_eq!(FastForestFireCellState::GROWTH_PROBABILITY, 0.05);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Component, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct ForestFireCellState<const GROWTH: u32, const LIGHTNING: u32>(pub ForestFireStage);
+
+/// Forest-fire cell state with a `0.01` growth probability and a `0.0001`
+/// lightning probability
+pub type DefaultForestFireCellState = ForestFireCellState<10_000, 100>;
+
+impl<const GROWTH: u32, const LIGHTNING: u32> ForestFireCellState<GROWTH, LIGHTNING> {
+    /// Probability for an empty cell to grow a tree
+    #[allow(clippy::cast_lossless)]
+    pub const GROWTH_PROBABILITY: f64 = GROWTH as f64 / 1_000_000.0;
+    /// Probability for a tree to be struck by lightning
+    #[allow(clippy::cast_lossless)]
+    pub const LIGHTNING_PROBABILITY: f64 = LIGHTNING as f64 / 1_000_000.0;
+    /// The empty state
+    pub const EMPTY: Self = Self(ForestFireStage::Empty);
+    /// The tree state
+    pub const TREE: Self = Self(ForestFireStage::Tree);
+    /// The burning tree state
+    pub const BURNING: Self = Self(ForestFireStage::Burning);
+}
+
+impl<const GROWTH: u32, const LIGHTNING: u32> CellState for ForestFireCellState<GROWTH, LIGHTNING> {
+    /// Empty and tree cells change randomly, every cell must be evaluated
+    const NEIGHBOR_DRIVEN: bool = false;
+
+    /// Deterministic evaluation, without growth nor lightning
+    fn new_cell_state<'a>(&self, mut neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        match self.0 {
+            ForestFireStage::Tree if neighbor_cells.any(|c| *c == Self::BURNING) => Self::BURNING,
+            ForestFireStage::Burning => Self::EMPTY,
+            _ => *self,
+        }
+    }
+
+    fn new_cell_state_with_context<'a, C: Cell>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+        context: &CellContext<C>,
+    ) -> Self {
+        let state = self.new_cell_state(neighbor_cells.filter_map(|(_, state)| state));
+        match (self.0, state.0) {
+            (ForestFireStage::Empty, _) if context.rng().chance(Self::GROWTH_PROBABILITY) => {
+                Self::TREE
+            }
+            (ForestFireStage::Tree, ForestFireStage::Tree)
+                if context.rng().chance(Self::LIGHTNING_PROBABILITY) =>
+            {
+                Self::BURNING
+            }
+            _ => state,
+        }
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        match self.0 {
+            ForestFireStage::Empty => None,
+            ForestFireStage::Tree => Some(Color::Srgba(FOREST_GREEN)),
+            ForestFireStage::Burning => Some(Color::Srgba(ORANGE_RED)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "2D"))]
+mod tests {
+    use super::*;
+    use crate::MooreCell2d;
+    use bevy::math::IVec2;
+    use std::time::Duration;
+
+    type State = ForestFireCellState<250_000, 50_000>;
+
+    /// Evaluates `state` for a 100x100 grid with the given `neighbors`,
+    /// returning the amount of cells in the `expected` state
+    fn count(state: State, neighbors: &[State], expected: State) -> usize {
+        let mut count = 0;
+        for x in 0..100 {
+            for y in 0..100 {
+                let cell = MooreCell2d::new(IVec2::new(x, y));
+                let context = CellContext::new(&cell, 5, Duration::ZERO).with_seed(42);
+                let neighbors = neighbors.iter().map(Some).enumerate();
+                if state.new_cell_state_with_context(neighbors, &context) == expected {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn deterministic_rules() {
+        let neighbors = [State::BURNING, State::EMPTY];
+        assert_eq!(count(State::BURNING, &neighbors, State::EMPTY), 10_000);
+        assert_eq!(count(State::TREE, &neighbors, State::BURNING), 10_000);
+    }
+
+    #[test]
+    fn growth_probability() {
+        let grown = count(State::EMPTY, &[State::BURNING], State::TREE);
+        // 2500 expected, the standard deviation is about 43
+        assert!((2_300..2_700).contains(&grown), "{grown} trees grew");
+    }
+
+    #[test]
+    fn lightning_probability() {
+        let burnt = count(State::TREE, &[State::TREE, State::EMPTY], State::BURNING);
+        // 500 expected, the standard deviation is about 22
+        assert!((400..600).contains(&burnt), "{burnt} trees burnt");
+    }
+}
@@ -0,0 +1,358 @@
+use crate::components::{CellState, LifeLikeRule, RuleParseError};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// "Generations" rule, a [`LifeLikeRule`] where dying cells go through
+/// several refractory states before being dead.
+///
+/// The rule can be parsed from the usual notations:
+///
+/// * **S/B/C** notation, like `/2/3` (Brian's Brain) or `345/2/4` (Star Wars)
+/// * **B/S/C** notation, like `B2/S/C3` or `B2/S345/G4`
+///
+/// The `C` value is the total amount of states, including the dead and
+/// alive states, between 2 and 255.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::GenerationsRule;
+/// let rule: GenerationsRule = "/2/3".parse().unwrap();
+/// assert!(rule.life.is_born(2));
+/// assert_eq!(rule.states, 3);
+/// assert_eq!(rule, "B2/S/C3".parse().unwrap());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct GenerationsRule {
+    /// The birth and survival conditions
+    pub life: LifeLikeRule,
+    /// Total amount of states, including the dead and alive states
+    pub states: u8,
+}
+
+impl GenerationsRule {
+    /// Brian's Brain rule (`/2/3`)
+    pub const BRIANS_BRAIN: Self = Self::from_notation("/2/3");
+    /// Star Wars rule (`345/2/4`)
+    pub const STAR_WARS: Self = Self::from_notation("345/2/4");
+
+    /// Parses a rule string in **S/B/C** or **B/S/C** notation
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleParseError`] if the rule is invalid
+    pub const fn parse(rule: &str) -> Result<Self, RuleParseError> {
+        let bytes = rule.as_bytes();
+        if bytes.is_empty() {
+            return Err(RuleParseError::InvalidFormat);
+        }
+        let letters = matches!(bytes[0], b'B' | b'b' | b'S' | b's');
+        // Position of the state count section
+        let mut i = bytes.len();
+        while i > 0 {
+            if letters && matches!(bytes[i - 1], b'C' | b'c' | b'G' | b'g') {
+                break;
+            }
+            if !letters && bytes[i - 1] == b'/' {
+                break;
+            }
+            i -= 1;
+        }
+        if i == 0 {
+            return Err(RuleParseError::InvalidFormat);
+        }
+        let (life, states) = bytes.split_at(i);
+        // Strips the state count prefix
+        let (life, _) = life.split_at(i - 1);
+        // Strips the optional separator
+        let life = match life.split_last() {
+            Some((&b'/', life)) if letters => life,
+            _ => life,
+        };
+        let life = if letters {
+            LifeLikeRule::parse_bs(life)
+        } else {
+            LifeLikeRule::parse_sb(life)
+        };
+        let life = match life {
+            Ok(life) => life,
+            Err(e) => return Err(e),
+        };
+        let states = match parse_state_count(states) {
+            Ok(states) => states,
+            Err(e) => return Err(e),
+        };
+        Ok(Self { life, states })
+    }
+
+    /// Parses a rule string in **S/B/C** or **B/S/C** notation, meant for
+    /// constant evaluation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn from_notation(rule: &str) -> Self {
+        match Self::parse(rule) {
+            Ok(rule) => rule,
+            Err(_) => panic!("Invalid generations rule"),
+        }
+    }
+
+    /// Parses a rule string in **S/B/C** or **B/S/C** notation and encodes
+    /// it, to be used as a [`GenerationsCellState`] const argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn bits(rule: &str) -> u128 {
+        Self::from_notation(rule).to_bits()
+    }
+
+    /// Encodes the rule in a single `u128`
+    #[must_use]
+    #[inline]
+    pub const fn to_bits(self) -> u128 {
+        self.life.to_bits() as u128 | ((self.states as u128) << 64)
+    }
+
+    /// Decodes a rule encoded with [`Self::to_bits`]
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn from_bits(bits: u128) -> Self {
+        Self {
+            life: LifeLikeRule::from_bits(bits as u64),
+            states: (bits >> 64) as u8,
+        }
+    }
+}
+
+/// Parses the state count section of a generations rule
+const fn parse_state_count(bytes: &[u8]) -> Result<u8, RuleParseError> {
+    if bytes.is_empty() {
+        return Err(RuleParseError::InvalidFormat);
+    }
+    let mut count: u32 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            c @ b'0'..=b'9' => count = count * 10 + (c - b'0') as u32,
+            c => return Err(RuleParseError::InvalidCharacter(c as char)),
+        }
+        if count > u8::MAX as u32 {
+            return Err(RuleParseError::OutOfRange);
+        }
+        i += 1;
+    }
+    if count < 2 {
+        return Err(RuleParseError::OutOfRange);
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(count as u8)
+}
+
+impl FromStr for GenerationsRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for GenerationsRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/C{}", self.life, self.states)
+    }
+}
+
+/// "Generations" cellular automaton state, following the [`GenerationsRule`]
+/// encoded in the `RULE` const argument (see [`GenerationsRule::bits`]).
+///
+/// The inner value is the cell stage:
+///
+/// * `0` is a dead cell, born if its amount of live neighbors matches the rule
+///   birth conditions
+/// * `1` is a live cell, which starts dying if its amount of live neighbors
+///   doesn't match the rule survival conditions
+/// * Higher values are dying cells, which may not be born or survive and
+///   advance a stage every generation until they are dead
+///
+/// Only live cells are counted as live neighbors.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct GenerationsCellState<const RULE: u128>(pub u8);
+
+/// Brian's Brain (`/2/3`) cell state
+pub type BriansBrainCellState = GenerationsCellState<{ GenerationsRule::BRIANS_BRAIN.to_bits() }>;
+/// Star Wars (`345/2/4`) cell state
+pub type StarWarsCellState = GenerationsCellState<{ GenerationsRule::STAR_WARS.to_bits() }>;
+
+impl<const RULE: u128> GenerationsCellState<RULE> {
+    /// The state rule
+    pub const RULE: GenerationsRule = GenerationsRule::from_bits(RULE);
+    /// The dead state
+    pub const DEAD: Self = Self(0);
+    /// The live state
+    pub const ALIVE: Self = Self(1);
+
+    /// Is the cell dead
+    #[must_use]
+    #[inline]
+    pub const fn is_dead(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Is the cell alive
+    #[must_use]
+    #[inline]
+    pub const fn is_alive(&self) -> bool {
+        self.0 == 1
+    }
+
+    /// Is the cell dying
+    #[must_use]
+    #[inline]
+    pub const fn is_dying(&self) -> bool {
+        self.0 > 1
+    }
+
+    /// Retrieves the next dying stage, or the dead state
+    const fn decay(self) -> Self {
+        let next = self.0.saturating_add(1);
+        if next < Self::RULE.states {
+            Self(next)
+        } else {
+            Self::DEAD
+        }
+    }
+}
+
+impl<const RULE: u128> CellState for GenerationsCellState<RULE> {
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        if self.is_dying() {
+            return self.decay();
+        }
+        let alive_cells_count = neighbor_cells.filter(|c| c.is_alive()).count();
+        match (self.is_alive(), alive_cells_count) {
+            (true, count) if Self::RULE.life.survives(count) => Self::ALIVE,
+            (true, _) => self.decay(),
+            (false, count) if Self::RULE.life.is_born(count) => Self::ALIVE,
+            (false, _) => Self::DEAD,
+        }
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        match self.0 {
+            0 => None,
+            1 => Some(Color::WHITE),
+            stage => {
+                let t = f32::from(stage - 1) / f32::from(Self::RULE.states - 1);
+                let c = 1.0 - t;
+                Some(Color::srgb(c * 0.5, c * 0.5, c))
+            }
+        }
+    }
+}
+
+impl<const RULE: u128> From<bool> for GenerationsCellState<RULE> {
+    fn from(val: bool) -> Self {
+        if val {
+            Self::ALIVE
+        } else {
+            Self::DEAD
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_notations() {
+        let brians_brain = GenerationsRule {
+            life: LifeLikeRule {
+                birth: 0b100,
+                survival: 0,
+            },
+            states: 3,
+        };
+        assert_eq!(GenerationsRule::parse("/2/3"), Ok(brians_brain));
+        assert_eq!(GenerationsRule::parse("B2/S/C3"), Ok(brians_brain));
+        assert_eq!(GenerationsRule::parse("B2/SC3"), Ok(brians_brain));
+        assert_eq!(GenerationsRule::parse("b2/s/g3"), Ok(brians_brain));
+        let star_wars = GenerationsRule::STAR_WARS;
+        assert_eq!(star_wars.life, LifeLikeRule::parse("B2/S345").unwrap());
+        assert_eq!(star_wars.states, 4);
+        assert_eq!(GenerationsRule::parse("B2/S345/C4"), Ok(star_wars));
+        assert_eq!(
+            GenerationsRule::parse("23/3/256"),
+            Err(RuleParseError::OutOfRange)
+        );
+        assert_eq!(
+            GenerationsRule::parse("23/3/1"),
+            Err(RuleParseError::OutOfRange)
+        );
+        assert_eq!(
+            GenerationsRule::parse("B3/S23"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            GenerationsRule::parse("23/3"),
+            Err(RuleParseError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let rule = GenerationsRule::STAR_WARS;
+        assert_eq!(rule.to_string(), "B2/S345/C4");
+        assert_eq!(rule.to_string().parse(), Ok(rule));
+        assert_eq!(GenerationsRule::from_bits(rule.to_bits()), rule);
+    }
+
+    #[test]
+    fn brians_brain_decay() {
+        let alive = BriansBrainCellState::ALIVE;
+        let dead = BriansBrainCellState::DEAD;
+        let neighbors = [alive, alive, dead, dead];
+        // Birth
+        let state = dead.new_cell_state(neighbors.iter());
+        assert!(state.is_alive());
+        // Live cells never survive
+        let state = state.new_cell_state(neighbors.iter());
+        assert!(state.is_dying());
+        assert_eq!(state.0, 2);
+        // Dying cells can't be born again
+        let state = state.new_cell_state(neighbors.iter());
+        assert!(state.is_dead());
+    }
+
+    #[test]
+    fn star_wars_decay() {
+        let alive = StarWarsCellState::ALIVE;
+        let dead = StarWarsCellState::DEAD;
+        // Survival
+        let neighbors = [alive, alive, alive, dead];
+        assert!(alive.new_cell_state(neighbors.iter()).is_alive());
+        // Decay
+        let neighbors = [alive, dead, dead, dead];
+        let mut state = alive.new_cell_state(neighbors.iter());
+        assert_eq!(state.0, 2);
+        state = state.new_cell_state(neighbors.iter());
+        assert_eq!(state.0, 3);
+        state = state.new_cell_state(neighbors.iter());
+        assert!(state.is_dead());
+    }
+}
@@ -0,0 +1,511 @@
+use crate::components::{CellState, RuleParseError};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+/// Hensel letters of the neighborhood classes for 0 to 4 live neighbors.
+/// Classes for more than 4 live neighbors use the letters of their complement.
+const LETTERS: [&[u8]; 5] = [b"", b"ce", b"ceaikn", b"ceaiknjqry", b"ceaiknjqrytwz"];
+
+/// Representative arrangement of every neighborhood class for 0 to 4 live
+/// neighbors, matching [`LETTERS`].
+///
+/// The arrangements are bitmasks of a row-major 3x3 grid starting at the top
+/// left cell, the center bit is unused.
+const REPRESENTATIVES: [&[u16]; 5] = [
+    &[0],
+    &[1, 2],
+    &[5, 10, 3, 40, 33, 68],
+    &[69, 42, 11, 7, 98, 13, 14, 70, 41, 97],
+    &[325, 170, 15, 45, 99, 71, 106, 102, 43, 101, 105, 78, 108],
+];
+
+/// First class index for every live neighbor count
+const CLASS_OFFSETS: [usize; 10] = [0, 1, 3, 9, 19, 32, 42, 48, 50, 51];
+
+/// Class index of every live neighbor arrangement
+const CLASSES: [u8; 256] = neighborhood_classes();
+
+/// Amount of neighborhood classes for `count` live neighbors
+const fn class_count(count: usize) -> usize {
+    CLASS_OFFSETS[count + 1] - CLASS_OFFSETS[count]
+}
+
+/// Bitmask of every neighborhood class for `count` live neighbors
+const fn all_classes(count: usize) -> u64 {
+    ((1 << class_count(count)) - 1) << CLASS_OFFSETS[count]
+}
+
+/// Converts a 3x3 grid bitmask to a bitmask in
+/// [`MooreCell2d`](crate::MooreCell2d) neighbor order, which is clockwise
+/// starting from the left neighbor.
+const fn grid_to_neighbors(grid: u16) -> u8 {
+    // Neighbor index of every grid cell, the center is skipped
+    const INDICES: [u8; 9] = [1, 2, 3, 0, 0, 4, 7, 6, 5];
+    let mut mask = 0;
+    let mut i = 0;
+    while i < 9 {
+        if i != 4 && grid & (1 << i) != 0 {
+            mask |= 1 << INDICES[i];
+        }
+        i += 1;
+    }
+    mask
+}
+
+/// Reflects a neighbor bitmask along the horizontal axis
+const fn reflect(mask: u8) -> u8 {
+    let mut res = 0;
+    let mut i = 0;
+    while i < 8 {
+        if mask & (1 << i) != 0 {
+            res |= 1 << ((8 - i) % 8);
+        }
+        i += 1;
+    }
+    res
+}
+
+/// Computes the class index of every live neighbor arrangement, every
+/// rotation and reflection of an arrangement sharing the same class.
+#[allow(clippy::cast_possible_truncation)]
+const fn neighborhood_classes() -> [u8; 256] {
+    let mut classes = [0; 256];
+    let mut count = 0;
+    while count <= 8 {
+        let mut i = 0;
+        while i < class_count(count) {
+            let mask = if count <= 4 {
+                grid_to_neighbors(REPRESENTATIVES[count][i])
+            } else {
+                !grid_to_neighbors(REPRESENTATIVES[8 - count][i])
+            };
+            let class = (CLASS_OFFSETS[count] + i) as u8;
+            let mut symmetry = 0;
+            while symmetry < 8 {
+                let image = if symmetry < 4 { mask } else { reflect(mask) };
+                // 90 degrees rotations
+                let image = image.rotate_left(2 * (symmetry % 4));
+                classes[image as usize] = class;
+                symmetry += 1;
+            }
+            i += 1;
+        }
+        count += 1;
+    }
+    classes
+}
+
+/// Isotropic non-totalistic rule, defining the arrangements of live neighbors
+/// for which a dead cell is born and a live cell survives.
+///
+/// The 256 possible arrangements of the 8 [Moore] neighbors are grouped in 51
+/// classes, invariant under rotation and reflection, identified by their live
+/// neighbor count and a letter.
+///
+/// The rule is parsed from the [Hensel] notation, like `B2-a/S12` or
+/// `B2ce3/S23-k`:
+///
+/// * A neighbor count alone matches every class for this count
+/// * A neighbor count followed by letters only matches the given classes
+/// * A neighbor count followed by `-` and letters matches every class except
+///   the given ones
+///
+/// [Moore]: https://en.wikipedia.org/wiki/Moore_neighborhood
+/// [Hensel]: https://conwaylife.com/wiki/Isotropic_non-totalistic_rule
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::IsotropicRule;
+/// let rule: IsotropicRule = "B2-a/S12".parse().unwrap();
+/// assert_eq!(rule.to_string(), "B2ceikn/S12");
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct IsotropicRule {
+    /// Bitmask of the neighborhood classes giving birth to a dead cell
+    pub birth: u64,
+    /// Bitmask of the neighborhood classes allowing a live cell to survive
+    pub survival: u64,
+}
+
+impl IsotropicRule {
+    /// Parses a rule string in Hensel notation
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleParseError`] if the rule is invalid
+    pub const fn parse(rule: &str) -> Result<Self, RuleParseError> {
+        let bytes = rule.as_bytes();
+        if bytes.is_empty() || !matches!(bytes[0], b'B' | b'b' | b'S' | b's') {
+            return Err(RuleParseError::InvalidFormat);
+        }
+        let mut rule = Self {
+            birth: 0,
+            survival: 0,
+        };
+        let (mut birth_seen, mut survival_seen) = (false, false);
+        let mut birth_section = true;
+        // Current neighbor count, with its listed classes and negation
+        let mut count = None;
+        let mut classes: u64 = 0;
+        let mut negated = false;
+        let mut i = 0;
+        while i <= bytes.len() {
+            let c = if i < bytes.len() { bytes[i] } else { b'/' };
+            if let (Some(n), b'B' | b'b' | b'S' | b's' | b'/' | b'0'..=b'9') = (count, c) {
+                let mask = match (classes, negated) {
+                    (0, true) => return Err(RuleParseError::InvalidFormat),
+                    (0, false) => all_classes(n),
+                    (classes, true) => all_classes(n) & !classes,
+                    (classes, false) => classes,
+                };
+                if birth_section {
+                    rule.birth |= mask;
+                } else {
+                    rule.survival |= mask;
+                }
+                count = None;
+                classes = 0;
+                negated = false;
+            }
+            match c {
+                b'B' | b'b' => {
+                    if birth_seen {
+                        return Err(RuleParseError::DuplicateSection('B'));
+                    }
+                    birth_seen = true;
+                    birth_section = true;
+                }
+                b'S' | b's' => {
+                    if survival_seen {
+                        return Err(RuleParseError::DuplicateSection('S'));
+                    }
+                    survival_seen = true;
+                    birth_section = false;
+                }
+                b'/' => (),
+                b'9' => return Err(RuleParseError::OutOfRange),
+                c @ b'0'..=b'8' => count = Some((c - b'0') as usize),
+                b'-' if count.is_some() && classes == 0 && !negated => negated = true,
+                c => {
+                    let Some(n) = count else {
+                        return Err(RuleParseError::InvalidCharacter(c as char));
+                    };
+                    let letters = LETTERS[if n <= 4 { n } else { 8 - n }];
+                    let mut index = 0;
+                    while index < letters.len() && letters[index] != c {
+                        index += 1;
+                    }
+                    if index == letters.len() {
+                        return Err(RuleParseError::InvalidCharacter(c as char));
+                    }
+                    classes |= 1 << (CLASS_OFFSETS[n] + index);
+                }
+            }
+            i += 1;
+        }
+        Ok(rule)
+    }
+
+    /// Parses a rule string in Hensel notation, meant for constant
+    /// evaluation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn from_notation(rule: &str) -> Self {
+        match Self::parse(rule) {
+            Ok(rule) => rule,
+            Err(_) => panic!("Invalid isotropic rule"),
+        }
+    }
+
+    /// Parses a rule string in Hensel notation and encodes it, to be used as
+    /// an [`IsotropicCellState`] const argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn bits(rule: &str) -> u128 {
+        Self::from_notation(rule).to_bits()
+    }
+
+    /// Encodes the rule in a single `u128`
+    #[must_use]
+    #[inline]
+    pub const fn to_bits(self) -> u128 {
+        self.birth as u128 | ((self.survival as u128) << 64)
+    }
+
+    /// Decodes a rule encoded with [`Self::to_bits`]
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn from_bits(bits: u128) -> Self {
+        Self {
+            birth: bits as u64,
+            survival: (bits >> 64) as u64,
+        }
+    }
+
+    /// Retrieves the class of an arrangement of live neighbors, given as a
+    /// bitmask in [`MooreCell2d`](crate::MooreCell2d) neighbor order.
+    #[must_use]
+    #[inline]
+    pub const fn neighborhood_class(neighbors: u8) -> usize {
+        CLASSES[neighbors as usize] as usize
+    }
+
+    /// Is a dead cell with the given live `neighbors` born
+    #[must_use]
+    #[inline]
+    pub const fn is_born(&self, neighbors: u8) -> bool {
+        self.birth & (1 << Self::neighborhood_class(neighbors)) != 0
+    }
+
+    /// Does a live cell with the given live `neighbors` survive
+    #[must_use]
+    #[inline]
+    pub const fn survives(&self, neighbors: u8) -> bool {
+        self.survival & (1 << Self::neighborhood_class(neighbors)) != 0
+    }
+}
+
+impl FromStr for IsotropicRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for IsotropicRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (section, mask) in [('B', self.birth), ('S', self.survival)] {
+            if section == 'S' {
+                write!(f, "/")?;
+            }
+            write!(f, "{section}")?;
+            for count in 0..=8 {
+                let classes = mask & all_classes(count);
+                if classes == 0 {
+                    continue;
+                }
+                write!(f, "{count}")?;
+                if classes == all_classes(count) {
+                    continue;
+                }
+                let letters = LETTERS[count.min(8 - count)];
+                for (i, letter) in letters.iter().enumerate() {
+                    if classes & (1 << (CLASS_OFFSETS[count] + i)) != 0 {
+                        write!(f, "{}", *letter as char)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Isotropic non-totalistic cellular automaton state, following the
+/// [`IsotropicRule`] encoded in the `RULE` const argument (see
+/// [`IsotropicRule::bits`]).
+///
+/// The rule depends on the arrangement of the neighbors, so this state must be
+/// used with [`MooreCell2d`](crate::MooreCell2d) cells. Missing neighbors are
+/// considered dead.
+///
+/// A dead cell is `false`, a live cell is `true`
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "2D")]
+/// # use bevy_life::{CellularAutomatonPlugin, IsotropicCellState, IsotropicRule, MooreCell2d};
+/// # #[cfg(feature = "2D")]
+/// type TlifeCellState = IsotropicCellState<{ IsotropicRule::bits("B3/S2-i34q") }>;
+/// # #[cfg(feature = "2D")]
+/// type TlifePlugin = CellularAutomatonPlugin<MooreCell2d, TlifeCellState>;
+/// ```
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct IsotropicCellState<const RULE: u128>(pub bool);
+
+impl<const RULE: u128> IsotropicCellState<RULE> {
+    /// The state rule
+    pub const RULE: IsotropicRule = IsotropicRule::from_bits(RULE);
+}
+
+impl<const RULE: u128> CellState for IsotropicCellState<RULE> {
+    /// Considers the `neighbor_cells` to be ordered without any missing
+    /// neighbor, prefer [`Self::new_cell_state_positional`]
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        self.new_cell_state_positional(neighbor_cells.map(Some).enumerate())
+    }
+
+    fn new_cell_state_positional<'a>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+    ) -> Self {
+        let neighbors = neighbor_cells
+            .filter(|(i, cell)| *i < 8 && cell.is_some_and(|c| c.0))
+            .fold(0, |mask, (i, _)| mask | (1 << i));
+        let alive = if self.0 {
+            Self::RULE.survives(neighbors)
+        } else {
+            Self::RULE.is_born(neighbors)
+        };
+        Self(alive)
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        if self.0 {
+            Some(Color::WHITE)
+        } else {
+            None
+        }
+    }
+}
+
+impl<const RULE: u128> Deref for IsotropicCellState<RULE> {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const RULE: u128> DerefMut for IsotropicCellState<RULE> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const RULE: u128> From<bool> for IsotropicCellState<RULE> {
+    fn from(val: bool) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConwayCellState, LifeLikeRule};
+
+    #[test]
+    fn neighborhood_classes() {
+        let mut seen = [false; 51];
+        for neighbors in 0..=u8::MAX {
+            let class = IsotropicRule::neighborhood_class(neighbors);
+            let count = neighbors.count_ones() as usize;
+            assert!((CLASS_OFFSETS[count]..CLASS_OFFSETS[count + 1]).contains(&class));
+            // Rotations and reflections share the same class
+            assert_eq!(
+                IsotropicRule::neighborhood_class(neighbors.rotate_left(2)),
+                class
+            );
+            assert_eq!(IsotropicRule::neighborhood_class(reflect(neighbors)), class);
+            seen[class] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn parse_notation() {
+        let rule = IsotropicRule::parse("B2-a/S12").unwrap();
+        assert_eq!(rule.birth, all_classes(2) & !(1 << 5));
+        assert_eq!(rule.survival, all_classes(1) | all_classes(2));
+        assert_eq!(IsotropicRule::parse("B2ceikn/S12"), Ok(rule));
+        assert_eq!(rule.to_string().parse(), Ok(rule));
+        assert_eq!(IsotropicRule::from_bits(rule.to_bits()), rule);
+        let rule = IsotropicRule::parse("B3/S2-i34q").unwrap();
+        assert_eq!(rule.to_string(), "B3/S2ceakn34q");
+        assert_eq!(
+            IsotropicRule::parse("B2x/S12"),
+            Err(RuleParseError::InvalidCharacter('x'))
+        );
+        assert_eq!(
+            IsotropicRule::parse("B1a/S12"),
+            Err(RuleParseError::InvalidCharacter('a'))
+        );
+        assert_eq!(
+            IsotropicRule::parse("B2-/S12"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            IsotropicRule::parse("B9/S12"),
+            Err(RuleParseError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn totalistic_equivalence() {
+        type State = IsotropicCellState<{ IsotropicRule::bits("B3/S23") }>;
+        let rule = IsotropicRule::from_notation("B3/S23");
+        let life = LifeLikeRule::CONWAY;
+        for neighbors in 0..=u8::MAX {
+            let count = neighbors.count_ones() as usize;
+            assert_eq!(rule.is_born(neighbors), life.is_born(count));
+            assert_eq!(rule.survives(neighbors), life.survives(count));
+        }
+        let neighbors = [true, false, true, false, false, true, false, false];
+        let states: Vec<_> = neighbors.map(State::from).to_vec();
+        let conway_states: Vec<_> = neighbors.map(ConwayCellState).to_vec();
+        assert_eq!(
+            State::from(false).new_cell_state(states.iter()).0,
+            ConwayCellState::from(false)
+                .new_cell_state(conway_states.iter())
+                .0,
+        );
+    }
+
+    #[test]
+    fn arrangement_dependent() {
+        type State = IsotropicCellState<{ IsotropicRule::bits("B2-a/S") }>;
+        let dead = State::from(false);
+        let alive = State::from(true);
+        // Left and top left neighbors (2a)
+        let adjacent = [
+            Some(&alive),
+            Some(&alive),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ];
+        assert!(
+            !dead
+                .new_cell_state_positional(adjacent.into_iter().enumerate())
+                .0
+        );
+        // Left and right neighbors (2i)
+        let opposite = [
+            Some(&alive),
+            None,
+            None,
+            None,
+            Some(&alive),
+            None,
+            None,
+            None,
+        ];
+        assert!(
+            dead.new_cell_state_positional(opposite.into_iter().enumerate())
+                .0
+        );
+    }
+}
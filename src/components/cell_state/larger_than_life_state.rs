@@ -0,0 +1,607 @@
+use crate::components::{Cell, CellState, NeighborhoodShape, RuleParseError};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Larger-than-Life rule, a totalistic rule on large neighborhoods where cells
+/// are born and survive if their amount of live neighbors is within a range.
+///
+/// The rule is parsed from the usual `Rr,Cc,Mm,Smin..max,Bmin..max,Nn`
+/// notation:
+///
+/// * `R` - The neighborhood range, up to `255` (optional)
+/// * `C` - The amount of states, `2` for classic two-state rules (`0` is an
+///   alias of `2`), higher values add decaying states like
+///   [`GenerationsRule`] (optional)
+/// * `M` - `1` if the middle cell counts as a neighbor, `0` otherwise
+///   (optional)
+/// * `S` - The survival range of live neighbors (required)
+/// * `B` - The birth range of live neighbors (required)
+/// * `N` - The neighborhood shape, `M` for Moore, `N` for von Neumann, `C` for
+///   circular and `+` for cross (optional)
+///
+/// # Note
+///
+/// The neighborhood is defined by the cell type, the matching ranged cell must
+/// be used, like [`MooreRangeCell2d<5>`](crate::MooreRangeCell2d) for
+/// `R5,NM`. The `R` and `N` sections, if given, are checked against the cell
+/// type when the simulation plugin is built.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{LargerThanLifeRule, NeighborhoodShape};
+/// let rule: LargerThanLifeRule = "R5,C0,M1,S34..58,B34..45,NM".parse().unwrap();
+/// assert!(rule.middle);
+/// assert_eq!(rule.range, Some(5));
+/// assert_eq!(rule.shape, Some(NeighborhoodShape::Moore));
+/// assert_eq!(rule.survival, (34, 58));
+/// assert_eq!(rule.to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+/// ```
+///
+/// [`GenerationsRule`]: crate::GenerationsRule
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct LargerThanLifeRule {
+    /// The neighborhood range, if defined by the rule
+    pub range: Option<u8>,
+    /// The neighborhood shape, if defined by the rule
+    pub shape: Option<NeighborhoodShape>,
+    /// Total amount of states, including the dead and alive states
+    pub states: u8,
+    /// Does the middle cell count as a neighbor
+    pub middle: bool,
+    /// Inclusive range of live neighbors allowing a live cell to survive
+    pub survival: (u16, u16),
+    /// Inclusive range of live neighbors giving birth to a dead cell
+    pub birth: (u16, u16),
+}
+
+impl LargerThanLifeRule {
+    /// Bugs rule (`R5,C0,M1,S34..58,B34..45,NM`)
+    pub const BUGS: Self = Self::from_notation("R5,C0,M1,S34..58,B34..45,NM");
+    /// Majority rule (`R4,C0,M1,S41..81,B41..81,NM`)
+    pub const MAJORITY: Self = Self::from_notation("R4,C0,M1,S41..81,B41..81,NM");
+
+    /// Parses a rule string in `Rr,Cc,Mm,Smin..max,Bmin..max,Nn` notation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleParseError`] if the rule is invalid
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn parse(rule: &str) -> Result<Self, RuleParseError> {
+        let bytes = rule.as_bytes();
+        let mut rule = Self {
+            range: None,
+            shape: None,
+            states: 2,
+            middle: false,
+            survival: (0, 0),
+            birth: (0, 0),
+        };
+        let (mut states_seen, mut middle_seen) = (false, false);
+        let (mut survival_seen, mut birth_seen) = (false, false);
+        let mut start = 0;
+        while start < bytes.len() {
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b',' {
+                end += 1;
+            }
+            let (_, token) = bytes.split_at(start);
+            let (token, _) = token.split_at(end - start);
+            let Some((&key, value)) = token.split_first() else {
+                return Err(RuleParseError::InvalidFormat);
+            };
+            match key {
+                b'R' | b'r' => {
+                    if rule.range.is_some() {
+                        return Err(RuleParseError::DuplicateSection('R'));
+                    }
+                    rule.range = match parse_number(value, u8::MAX as u16) {
+                        Ok(0) => return Err(RuleParseError::InvalidFormat),
+                        Ok(range) => Some(range as u8),
+                        Err(e) => return Err(e),
+                    };
+                }
+                b'C' | b'c' => {
+                    if states_seen {
+                        return Err(RuleParseError::DuplicateSection('C'));
+                    }
+                    states_seen = true;
+                    rule.states = match parse_number(value, u8::MAX as u16) {
+                        Ok(0) => 2,
+                        Ok(1) => return Err(RuleParseError::OutOfRange),
+                        Ok(states) => states as u8,
+                        Err(e) => return Err(e),
+                    };
+                }
+                b'M' | b'm' => {
+                    if middle_seen {
+                        return Err(RuleParseError::DuplicateSection('M'));
+                    }
+                    middle_seen = true;
+                    rule.middle = match value {
+                        b"0" => false,
+                        b"1" => true,
+                        _ => return Err(RuleParseError::InvalidFormat),
+                    };
+                }
+                b'S' | b's' => {
+                    if survival_seen {
+                        return Err(RuleParseError::DuplicateSection('S'));
+                    }
+                    survival_seen = true;
+                    rule.survival = match parse_interval(value) {
+                        Ok(interval) => interval,
+                        Err(e) => return Err(e),
+                    };
+                }
+                b'B' | b'b' => {
+                    if birth_seen {
+                        return Err(RuleParseError::DuplicateSection('B'));
+                    }
+                    birth_seen = true;
+                    rule.birth = match parse_interval(value) {
+                        Ok(interval) => interval,
+                        Err(e) => return Err(e),
+                    };
+                }
+                b'N' | b'n' => {
+                    if rule.shape.is_some() {
+                        return Err(RuleParseError::DuplicateSection('N'));
+                    }
+                    rule.shape = Some(match value {
+                        b"M" | b"m" => NeighborhoodShape::Moore,
+                        b"N" | b"n" => NeighborhoodShape::VonNeumann,
+                        b"C" | b"c" => NeighborhoodShape::Circular,
+                        b"+" => NeighborhoodShape::Cross,
+                        _ => return Err(RuleParseError::InvalidFormat),
+                    });
+                }
+                c => return Err(RuleParseError::InvalidCharacter(c as char)),
+            }
+            start = end + 1;
+        }
+        if !survival_seen || !birth_seen {
+            return Err(RuleParseError::InvalidFormat);
+        }
+        Ok(rule)
+    }
+
+    /// Parses a rule string in `Rr,Cc,Mm,Smin..max,Bmin..max,Nn` notation,
+    /// meant for constant evaluation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn from_notation(rule: &str) -> Self {
+        match Self::parse(rule) {
+            Ok(rule) => rule,
+            Err(_) => panic!("Invalid larger than life rule"),
+        }
+    }
+
+    /// Parses a rule string in `Rr,Cc,Mm,Smin..max,Bmin..max,Nn` notation and
+    /// encodes it, to be used as a [`LargerThanLifeCellState`] const argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn bits(rule: &str) -> u128 {
+        Self::from_notation(rule).to_bits()
+    }
+
+    /// Encodes the rule in a single `u128`
+    #[must_use]
+    pub const fn to_bits(self) -> u128 {
+        let range = match self.range {
+            Some(range) => range as u128,
+            None => 0,
+        };
+        let shape = match self.shape {
+            None => 0,
+            Some(NeighborhoodShape::Moore) => 1,
+            Some(NeighborhoodShape::VonNeumann) => 2,
+            Some(NeighborhoodShape::Circular) => 3,
+            Some(NeighborhoodShape::Cross) => 4,
+        };
+        self.states as u128
+            | (self.middle as u128) << 16
+            | (self.survival.0 as u128) << 32
+            | (self.survival.1 as u128) << 48
+            | (self.birth.0 as u128) << 64
+            | (self.birth.1 as u128) << 80
+            | range << 96
+            | shape << 104
+    }
+
+    /// Decodes a rule encoded with [`Self::to_bits`]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn from_bits(bits: u128) -> Self {
+        Self {
+            range: match (bits >> 96) as u8 {
+                0 => None,
+                range => Some(range),
+            },
+            shape: match (bits >> 104) as u8 {
+                1 => Some(NeighborhoodShape::Moore),
+                2 => Some(NeighborhoodShape::VonNeumann),
+                3 => Some(NeighborhoodShape::Circular),
+                4 => Some(NeighborhoodShape::Cross),
+                _ => None,
+            },
+            states: bits as u8,
+            middle: (bits >> 16) & 1 == 1,
+            survival: ((bits >> 32) as u16, (bits >> 48) as u16),
+            birth: ((bits >> 64) as u16, (bits >> 80) as u16),
+        }
+    }
+
+    /// Is a dead cell with `count` live neighbors born
+    #[must_use]
+    #[inline]
+    pub const fn is_born(&self, count: usize) -> bool {
+        self.birth.0 as usize <= count && count <= self.birth.1 as usize
+    }
+
+    /// Does a live cell with `count` live neighbors survive
+    #[must_use]
+    #[inline]
+    pub const fn survives(&self, count: usize) -> bool {
+        self.survival.0 as usize <= count && count <= self.survival.1 as usize
+    }
+}
+
+/// Parses a decimal number, up to `max`
+const fn parse_number(bytes: &[u8], max: u16) -> Result<u16, RuleParseError> {
+    if bytes.is_empty() {
+        return Err(RuleParseError::InvalidFormat);
+    }
+    let mut value: u32 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            c @ b'0'..=b'9' => value = value * 10 + (c - b'0') as u32,
+            c => return Err(RuleParseError::InvalidCharacter(c as char)),
+        }
+        if value > max as u32 {
+            return Err(RuleParseError::OutOfRange);
+        }
+        i += 1;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(value as u16)
+}
+
+/// Parses a `min..max` interval
+const fn parse_interval(bytes: &[u8]) -> Result<(u16, u16), RuleParseError> {
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        if bytes[i] == b'.' && bytes[i + 1] == b'.' {
+            let (min, max) = bytes.split_at(i);
+            let (_, max) = max.split_at(2);
+            let min = match parse_number(min, u16::MAX) {
+                Ok(min) => min,
+                Err(e) => return Err(e),
+            };
+            let max = match parse_number(max, u16::MAX) {
+                Ok(max) => max,
+                Err(e) => return Err(e),
+            };
+            return Ok((min, max));
+        }
+        i += 1;
+    }
+    Err(RuleParseError::InvalidFormat)
+}
+
+impl FromStr for LargerThanLifeRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for LargerThanLifeRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(range) = self.range {
+            write!(f, "R{range},")?;
+        }
+        write!(
+            f,
+            "C{},M{},S{}..{},B{}..{}",
+            if self.states == 2 { 0 } else { self.states },
+            u8::from(self.middle),
+            self.survival.0,
+            self.survival.1,
+            self.birth.0,
+            self.birth.1,
+        )?;
+        match self.shape {
+            None => Ok(()),
+            Some(NeighborhoodShape::Moore) => write!(f, ",NM"),
+            Some(NeighborhoodShape::VonNeumann) => write!(f, ",NN"),
+            Some(NeighborhoodShape::Circular) => write!(f, ",NC"),
+            Some(NeighborhoodShape::Cross) => write!(f, ",N+"),
+        }
+    }
+}
+
+/// Larger-than-Life cellular automaton state, following the
+/// [`LargerThanLifeRule`] encoded in the `RULE` const argument (see
+/// [`LargerThanLifeRule::bits`]).
+///
+/// The inner value is the cell stage, `0` for a dead cell, `1` for a live cell
+/// and higher values for dying cells when the rule has more than two states.
+/// Only live cells are counted as live neighbors.
+///
+/// The neighborhood is defined by the cell type, this state must be used with
+/// a ranged cell matching the rule notation `R` and `N` sections, like
+/// [`MooreRangeCell2d<5>`](crate::MooreRangeCell2d) for `R5,NM`.
+///
+/// # Panics
+///
+/// Building a simulation plugin panics if the cell type doesn't match the rule
+/// `R` range or `N` shape, see [`Cell::neighborhood`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct LargerThanLifeCellState<const RULE: u128>(pub u8);
+
+/// Bugs (`R5,C0,M1,S34..58,B34..45,NM`) cell state
+pub type BugsCellState = LargerThanLifeCellState<{ LargerThanLifeRule::BUGS.to_bits() }>;
+/// Majority (`R4,C0,M1,S41..81,B41..81,NM`) cell state
+pub type MajorityCellState = LargerThanLifeCellState<{ LargerThanLifeRule::MAJORITY.to_bits() }>;
+
+impl<const RULE: u128> LargerThanLifeCellState<RULE> {
+    /// The state rule
+    pub const RULE: LargerThanLifeRule = LargerThanLifeRule::from_bits(RULE);
+    /// The dead state
+    pub const DEAD: Self = Self(0);
+    /// The live state
+    pub const ALIVE: Self = Self(1);
+
+    /// Is the cell alive
+    #[must_use]
+    #[inline]
+    pub const fn is_alive(&self) -> bool {
+        self.0 == 1
+    }
+
+    /// Retrieves the next dying stage, or the dead state
+    const fn decay(self) -> Self {
+        let next = self.0.saturating_add(1);
+        if next < Self::RULE.states {
+            Self(next)
+        } else {
+            Self::DEAD
+        }
+    }
+}
+
+impl<const RULE: u128> CellState for LargerThanLifeCellState<RULE> {
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        if self.0 > 1 {
+            return self.decay();
+        }
+        let mut alive_cells_count = neighbor_cells.filter(|c| c.is_alive()).count();
+        if Self::RULE.middle && self.is_alive() {
+            alive_cells_count += 1;
+        }
+        match (self.is_alive(), alive_cells_count) {
+            (true, count) if Self::RULE.survives(count) => Self::ALIVE,
+            (true, _) => self.decay(),
+            (false, count) if Self::RULE.is_born(count) => Self::ALIVE,
+            (false, _) => Self::DEAD,
+        }
+    }
+
+    fn check_cell_type<C: Cell>() {
+        let rule = Self::RULE;
+        if rule.range.is_none() && rule.shape.is_none() {
+            return;
+        }
+        let matches = C::neighborhood().is_some_and(|(shape, range)| {
+            rule.range.is_none_or(|r| i32::from(r) == range)
+                && rule.shape.is_none_or(|s| s == shape)
+        });
+        assert!(
+            matches,
+            "The `{rule}` Larger-than-Life rule doesn't match the `{}` cell neighborhood ({:?})",
+            std::any::type_name::<C>(),
+            C::neighborhood(),
+        );
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        match self.0 {
+            0 => None,
+            1 => Some(Color::WHITE),
+            stage => {
+                let t = f32::from(stage - 1) / f32::from(Self::RULE.states - 1);
+                let c = 1.0 - t;
+                Some(Color::srgb(c * 0.5, c * 0.5, c))
+            }
+        }
+    }
+}
+
+impl<const RULE: u128> From<bool> for LargerThanLifeCellState<RULE> {
+    fn from(val: bool) -> Self {
+        if val {
+            Self::ALIVE
+        } else {
+            Self::DEAD
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_notation() {
+        let bugs = LargerThanLifeRule::BUGS;
+        assert_eq!(bugs.range, Some(5));
+        assert_eq!(bugs.shape, Some(NeighborhoodShape::Moore));
+        assert_eq!(bugs.states, 2);
+        assert!(bugs.middle);
+        assert_eq!(bugs.survival, (34, 58));
+        assert_eq!(bugs.birth, (34, 45));
+        assert_eq!(
+            LargerThanLifeRule::parse("r5,m1,s34..58,b34..45,nm"),
+            Ok(bugs)
+        );
+        // The range and shape are optional
+        let rule = LargerThanLifeRule::parse("M1,S34..58,B34..45").unwrap();
+        assert_eq!((rule.range, rule.shape), (None, None));
+        assert_eq!(
+            LargerThanLifeRule {
+                range: Some(5),
+                shape: Some(NeighborhoodShape::Moore),
+                ..rule
+            },
+            bugs
+        );
+        let rule = LargerThanLifeRule::parse("R2,C3,M0,S1..2,B3..3,NC").unwrap();
+        assert_eq!(rule.range, Some(2));
+        assert_eq!(rule.shape, Some(NeighborhoodShape::Circular));
+        assert_eq!(rule.states, 3);
+        assert!(!rule.middle);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            LargerThanLifeRule::parse("R5,S34..58"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("R5,S34..5x,B34..45"),
+            Err(RuleParseError::InvalidCharacter('x'))
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("R500,S34..58,B34..45"),
+            Err(RuleParseError::OutOfRange)
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("R5,R5,S34..58,B34..45"),
+            Err(RuleParseError::DuplicateSection('R'))
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("R5,S34..58,B34..45,X1"),
+            Err(RuleParseError::InvalidCharacter('X'))
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("R0,S34..58,B34..45"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("R5,S34..58,B34..45,NX"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("S34..58,B34..45,NM,NN"),
+            Err(RuleParseError::DuplicateSection('N'))
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("R5,C2,C3,S34..58,B34..45"),
+            Err(RuleParseError::DuplicateSection('C'))
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("M0,M1,S34..58,B34..45"),
+            Err(RuleParseError::DuplicateSection('M'))
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("C1,S34..58,B34..45"),
+            Err(RuleParseError::OutOfRange)
+        );
+        assert_eq!(
+            LargerThanLifeRule::parse("C0,S1..2,B1..2").unwrap().states,
+            2
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for notation in [
+            "R5,C0,M1,S34..58,B34..45,NM",
+            "R4,C0,M1,S41..81,B41..81,NM",
+            "R2,C3,M0,S1..2,B3..3,NN",
+            "R7,C0,M0,S1..2,B3..3,NC",
+            "C0,M0,S1..2,B3..3,N+",
+            "C0,M1,S34..58,B34..45",
+        ] {
+            let rule = LargerThanLifeRule::from_notation(notation);
+            assert_eq!(rule.to_string(), notation);
+            assert_eq!(LargerThanLifeRule::from_bits(rule.to_bits()), rule);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    fn check_cell_type() {
+        type AnyRangeCellState =
+            LargerThanLifeCellState<{ LargerThanLifeRule::bits("M1,S34..58,B34..45") }>;
+        BugsCellState::check_cell_type::<crate::MooreRangeCell2d<5>>();
+        AnyRangeCellState::check_cell_type::<crate::MooreRangeCell2d<2>>();
+        AnyRangeCellState::check_cell_type::<crate::HexagonCell2d>();
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    #[should_panic(
+        expected = "The `R5,C0,M1,S34..58,B34..45,NM` Larger-than-Life rule doesn't match"
+    )]
+    fn mismatched_range() {
+        BugsCellState::check_cell_type::<crate::MooreCell2d>();
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    #[should_panic(expected = "Larger-than-Life rule doesn't match")]
+    fn mismatched_shape() {
+        BugsCellState::check_cell_type::<crate::NeumannRangeCell2d<5>>();
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    #[should_panic(expected = "Larger-than-Life rule doesn't match")]
+    fn mismatched_plugin() {
+        bevy::prelude::App::new().add_plugins(crate::CellularAutomatonPlugin::<
+            crate::MooreRangeCell2d<2>,
+            BugsCellState,
+        >::default());
+    }
+
+    #[test]
+    fn bugs_rule() {
+        let alive = BugsCellState::ALIVE;
+        let dead = BugsCellState::DEAD;
+        let neighbors = |count: usize| (0..120).map(move |i| BugsCellState::from(i < count));
+        let state = |cell: BugsCellState, count: usize| {
+            let neighbors: Vec<_> = neighbors(count).collect();
+            cell.new_cell_state(neighbors.iter())
+        };
+        // The middle cell counts as a neighbor
+        assert_eq!(state(alive, 33), alive);
+        assert_eq!(state(alive, 32), dead);
+        assert_eq!(state(alive, 57), alive);
+        assert_eq!(state(alive, 58), dead);
+        assert_eq!(state(dead, 33), dead);
+        assert_eq!(state(dead, 34), alive);
+        assert_eq!(state(dead, 45), alive);
+        assert_eq!(state(dead, 46), dead);
+    }
+}
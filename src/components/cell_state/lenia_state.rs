@@ -0,0 +1,414 @@
+use crate::components::{
+    Cell, CellContext, CellState, Neighborhood, NeighborhoodShape, RingNeighborhood,
+};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::{Component, IVec2};
+use std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
+
+/// Coloring of continuous cell states, with values between `0.0` and `1.0`.
+///
+/// Cells with a `0.0` value are hidden.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum ContinuousColoring {
+    /// From black to white
+    Grayscale,
+    /// From blue to red
+    #[default]
+    Heatmap,
+}
+
+impl ContinuousColoring {
+    #[cfg(feature = "auto-coloring")]
+    /// Color of a continuous state `value`
+    #[must_use]
+    pub fn color(self, value: f32) -> Option<Color> {
+        if value <= 0.0 {
+            return None;
+        }
+        let value = value.min(1.0);
+        match self {
+            Self::Grayscale => Some(Color::srgb(value, value, value)),
+            Self::Heatmap => Some(Color::hsl(240.0 * (1.0 - value), 1.0, 0.5)),
+        }
+    }
+}
+
+/// Lenia kernel, made of concentric rings with a smooth bump shaped profile.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LeniaKernel {
+    /// The kernel radius, in cells
+    pub radius: u8,
+    /// The peak height of every ring, from the center to the edge of the
+    /// kernel
+    pub peaks: &'static [f32],
+}
+
+impl LeniaKernel {
+    /// Weight of a neighbor at the given `distance` from the center cell,
+    /// between `0.0` and the ring peak height
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn weight(&self, distance: f32) -> f32 {
+        let r = distance / f32::from(self.radius);
+        if r <= 0.0 || r >= 1.0 || self.peaks.is_empty() {
+            return 0.0;
+        }
+        let ring_r = r * self.peaks.len() as f32;
+        self.peaks[ring_r as usize] * RingNeighborhood::bump(ring_r.fract())
+    }
+}
+
+/// Lenia growth function, a gaussian bump mapping the kernel weighted
+/// neighbor average to a growth rate between `-1.0` and `1.0`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LeniaGrowth {
+    /// The neighbor average with the maximal growth
+    pub mu: f32,
+    /// The width of the growth bump
+    pub sigma: f32,
+}
+
+impl LeniaGrowth {
+    /// Growth rate for the weighted neighbor average `u`
+    #[must_use]
+    pub fn growth(&self, u: f32) -> f32 {
+        let d = u - self.mu;
+        2.0f32.mul_add((-(d * d) / (2.0 * self.sigma * self.sigma)).exp(), -1.0)
+    }
+}
+
+/// Lenia rule parameters, to use with a [`LeniaCellState`].
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{LeniaCellState, LeniaGrowth, LeniaKernel, LeniaRule};
+/// /// Lenia rule with a two ring kernel
+/// struct TwoRingsRule;
+///
+/// impl LeniaRule for TwoRingsRule {
+///     const KERNEL: LeniaKernel = LeniaKernel {
+///         radius: 10,
+///         peaks: &[0.5, 1.0],
+///     };
+///     const GROWTH: LeniaGrowth = LeniaGrowth {
+///         mu: 0.26,
+///         sigma: 0.036,
+///     };
+///     const TIME_STEP: f32 = 0.1;
+/// }
+///
+/// type TwoRingsCellState = LeniaCellState<TwoRingsRule>;
+/// ```
+pub trait LeniaRule: Send + Sync + 'static {
+    /// The neighborhood kernel, its radius must match the cell range
+    const KERNEL: LeniaKernel;
+    /// The growth function
+    const GROWTH: LeniaGrowth;
+    /// The fraction of the growth rate applied every generation
+    const TIME_STEP: f32;
+    /// The neighborhood shape of the cell, defaults to
+    /// [`NeighborhoodShape::Circular`]
+    const NEIGHBORHOOD: NeighborhoodShape = NeighborhoodShape::Circular;
+    /// The state coloring, defaults to [`ContinuousColoring::Heatmap`]
+    const COLORING: ContinuousColoring = ContinuousColoring::Heatmap;
+}
+
+/// [`Neighborhood`] weighting the neighbors by the `R` [`LeniaRule`] kernel,
+/// see the [`LeniaRangeCell2d`](crate::LeniaRangeCell2d) cells.
+///
+/// The [`Neighborhood::weights`] are normalized so they sum to `1.0`.
+///
+/// # Panics
+///
+/// Computing the weights panics if the range doesn't match the kernel radius
+pub struct LeniaNeighborhood<R>(PhantomData<R>);
+
+impl<R: LeniaRule> Neighborhood for LeniaNeighborhood<R> {
+    const SHAPE: NeighborhoodShape = R::NEIGHBORHOOD;
+
+    fn weight(offset: IVec2, _range: i32) -> f32 {
+        R::KERNEL.weight(offset.as_vec2().length())
+    }
+
+    fn weights(range: i32) -> Vec<f32> {
+        assert_eq!(
+            range,
+            i32::from(R::KERNEL.radius),
+            "The cell range must match the `{}` kernel radius",
+            std::any::type_name::<R>(),
+        );
+        let weights: Vec<_> = Self::SHAPE
+            .offsets(range)
+            .map(|offset| Self::weight(offset, range))
+            .collect();
+        let total_weight: f32 = weights.iter().sum();
+        if total_weight <= 0.0 {
+            return weights;
+        }
+        weights.into_iter().map(|w| w / total_weight).collect()
+    }
+}
+
+impl<R> Debug for LeniaNeighborhood<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("LeniaNeighborhood")
+    }
+}
+
+impl<R> Clone for LeniaNeighborhood<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for LeniaNeighborhood<R> {}
+
+impl<R> Default for LeniaNeighborhood<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Orbium Lenia rule, a glider-like pattern found by Bert Chan
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct OrbiumRule;
+
+impl LeniaRule for OrbiumRule {
+    const KERNEL: LeniaKernel = LeniaKernel {
+        radius: 13,
+        peaks: &[1.0],
+    };
+    const GROWTH: LeniaGrowth = LeniaGrowth {
+        mu: 0.15,
+        sigma: 0.015,
+    };
+    const TIME_STEP: f32 = 0.1;
+}
+
+/// [Lenia](https://en.wikipedia.org/wiki/Lenia) continuous cellular automaton
+/// state, following the `R` [`LeniaRule`].
+///
+/// The state value is between `0.0` and `1.0`. Every generation the neighbor
+/// values are averaged, weighted by the rule [`LeniaKernel`], and the cell
+/// value grows by the [`LeniaGrowth`] rate of this average scaled by the rule
+/// time step. Missing neighbors count as `0.0` values.
+///
+/// This state must be used with cells weighted by the [`LeniaNeighborhood`] of
+/// the rule, with a range matching the kernel radius, like
+/// [`LeniaRangeCell2d<OrbiumRule, 13>`](crate::LeniaRangeCell2d) for the
+/// [`OrbiumRule`]. Building a simulation plugin panics with other cells.
+#[derive(Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct LeniaCellState<R: LeniaRule> {
+    /// The cell value, between `0.0` and `1.0`
+    pub value: f32,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    phantom: PhantomData<R>,
+}
+
+/// Orbium Lenia cell state
+pub type OrbiumCellState = LeniaCellState<OrbiumRule>;
+
+impl<R: LeniaRule> LeniaCellState<R> {
+    /// The empty state
+    pub const EMPTY: Self = Self::new(0.0);
+
+    /// Instantiates a new state with the given `value`, clamped between `0.0`
+    /// and `1.0`
+    #[must_use]
+    #[inline]
+    pub const fn new(value: f32) -> Self {
+        Self {
+            value: value.clamp(0.0, 1.0),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Computes the next state for the weighted neighbor `average`
+    fn next(self, average: f32) -> Self {
+        Self::new(R::TIME_STEP.mul_add(R::GROWTH.growth(average), self.value))
+    }
+}
+
+impl<R: LeniaRule> CellState for LeniaCellState<R> {
+    const WEIGHTED: bool = true;
+
+    /// Neighbors without their weight are all weighted equally
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        let (sum, count) = neighbor_cells.fold((0.0, 0_u16), |(sum, count), c| {
+            (sum + c.value, count.saturating_add(1))
+        });
+        let average = if count == 0 {
+            0.0
+        } else {
+            sum / f32::from(count)
+        };
+        self.next(average)
+    }
+
+    /// The weights are the normalized [`LeniaNeighborhood`] kernel
+    fn new_cell_state_weighted<'a>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (f32, Option<&'a Self>)>,
+    ) -> Self {
+        let average = neighbor_cells.fold(0.0, |sum, (weight, state)| {
+            weight.mul_add(state.map_or(0.0, |c| c.value), sum)
+        });
+        self.next(average)
+    }
+
+    fn new_cell_state_with_context<'a, C: Cell>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+        context: &CellContext<C>,
+    ) -> Self {
+        self.new_cell_state_weighted(
+            neighbor_cells.map(|(i, state)| (context.neighbor_weight(i), state)),
+        )
+    }
+
+    fn check_cell_type<C: Cell>() {
+        let kernel = LeniaNeighborhood::<R>::weights(i32::from(R::KERNEL.radius));
+        assert!(
+            C::neighbor_weights().is_some_and(|weights| weights == kernel),
+            "`LeniaCellState` requires cells weighted by the `{}` kernel, like `LeniaRangeCell2d`, not `{}` cells",
+            std::any::type_name::<R>(),
+            std::any::type_name::<C>(),
+        );
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        R::COLORING.color(self.value)
+    }
+}
+
+impl<R: LeniaRule> Debug for LeniaCellState<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LeniaCellState").field(&self.value).finish()
+    }
+}
+
+impl<R: LeniaRule> Clone for LeniaCellState<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: LeniaRule> Copy for LeniaCellState<R> {}
+
+impl<R: LeniaRule> PartialEq for LeniaCellState<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<R: LeniaRule> Default for LeniaCellState<R> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<R: LeniaRule> From<f32> for LeniaCellState<R> {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernel_weights() {
+        let kernel = OrbiumRule::KERNEL;
+        assert!((kernel.weight(6.5) - 1.0).abs() < 1e-6);
+        assert!(kernel.weight(0.0).abs() < f32::EPSILON);
+        assert!(kernel.weight(13.0).abs() < f32::EPSILON);
+        assert!(kernel.weight(3.0) < kernel.weight(5.0));
+        let rings = LeniaKernel {
+            radius: 10,
+            peaks: &[0.5, 1.0],
+        };
+        assert!((rings.weight(2.5) - 0.5).abs() < 1e-6);
+        assert!((rings.weight(7.5) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normalized_kernel() {
+        let kernel = LeniaNeighborhood::<OrbiumRule>::weights(13);
+        assert_eq!(kernel.len(), NeighborhoodShape::Circular.neighbor_count(13));
+        assert!((kernel.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    #[should_panic(expected = "must match")]
+    fn mismatched_kernel_radius() {
+        let _ = LeniaNeighborhood::<OrbiumRule>::weights(12);
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    fn check_cell_type() {
+        OrbiumCellState::check_cell_type::<crate::LeniaRangeCell2d<OrbiumRule, 13>>();
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    #[should_panic(expected = "`LeniaCellState` requires cells weighted by the")]
+    fn unweighted_plugin() {
+        bevy::prelude::App::new().add_plugins(crate::CellularAutomatonPlugin::<
+            crate::MooreCell2d,
+            OrbiumCellState,
+        >::default());
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    #[should_panic(expected = "`LeniaCellState` requires cells weighted by the")]
+    fn mismatched_kernel_weights() {
+        OrbiumCellState::check_cell_type::<crate::GaussianRangeCell2d<13>>();
+    }
+
+    #[test]
+    fn growth() {
+        let growth = OrbiumRule::GROWTH;
+        assert!((growth.growth(0.15) - 1.0).abs() < f32::EPSILON);
+        assert!((growth.growth(0.0) + 1.0).abs() < 1e-6);
+        assert!((growth.growth(1.0) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn orbium_rule() {
+        let kernel = LeniaNeighborhood::<OrbiumRule>::weights(13);
+        let state = |cell: f32, neighbor: f32| {
+            let neighbor = OrbiumCellState::new(neighbor);
+            let neighbors = kernel.iter().map(|&w| (w, Some(&neighbor)));
+            OrbiumCellState::new(cell).new_cell_state_weighted(neighbors)
+        };
+        // Empty areas stay empty
+        assert_eq!(state(0.0, 0.0), OrbiumCellState::EMPTY);
+        // Optimal neighborhood
+        assert!((state(0.5, 0.15).value - 0.6).abs() < 1e-5);
+        // Overcrowded neighborhood
+        assert!((state(0.5, 1.0).value - 0.4).abs() < 1e-5);
+        // Missing neighbors count as empty
+        let neighbor = OrbiumCellState::new(0.3);
+        let neighbors = kernel
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| (w, (i % 2 == 0).then_some(&neighbor)));
+        let value = OrbiumCellState::new(0.5)
+            .new_cell_state_weighted(neighbors)
+            .value;
+        assert!((value - 0.6).abs() < 1e-2, "{value}");
+    }
+}
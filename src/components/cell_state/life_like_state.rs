@@ -0,0 +1,420 @@
+use crate::components::CellState;
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+/// Error returned when parsing an invalid rule string
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RuleParseError {
+    /// The rule contains an unexpected character
+    InvalidCharacter(char),
+    /// A rule section (birth, survival, etc) is defined more than once
+    DuplicateSection(char),
+    /// A neighbor count or state count is out of the supported range
+    OutOfRange,
+    /// The rule doesn't match any supported notation
+    InvalidFormat,
+}
+
+impl Display for RuleParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => write!(f, "Invalid rule character `{c}`"),
+            Self::DuplicateSection(c) => write!(f, "Duplicate rule section `{c}`"),
+            Self::OutOfRange => write!(f, "Rule value out of range"),
+            Self::InvalidFormat => write!(f, "Invalid rule format"),
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+/// Life-like totalistic rule, defining the live neighbor counts for which a
+/// dead cell is born and a live cell survives.
+///
+/// The rule can be parsed from the usual notations:
+///
+/// * **B/S** notation, like `B36/S23` (`HighLife`) or `B3/S012345678`. The
+///   separator is optional (`B3S23`)
+/// * **S/B** notation, like `23/3` (Conway's Game of life)
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::LifeLikeRule;
+/// let rule: LifeLikeRule = "B36/S23".parse().unwrap();
+/// assert!(rule.is_born(6));
+/// assert!(rule.survives(2));
+/// assert_eq!(rule, "23/36".parse().unwrap());
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct LifeLikeRule {
+    /// Bitmask of the live neighbor counts giving birth to a dead cell
+    pub birth: u32,
+    /// Bitmask of the live neighbor counts allowing a live cell to survive
+    pub survival: u32,
+}
+
+impl LifeLikeRule {
+    /// Conway's Game of life rule (`B3/S23`)
+    pub const CONWAY: Self = Self::from_notation("B3/S23");
+    /// `HighLife` rule (`B36/S23`)
+    pub const HIGH_LIFE: Self = Self::from_notation("B36/S23");
+    /// Day & Night rule (`B3678/S34678`)
+    pub const DAY_AND_NIGHT: Self = Self::from_notation("B3678/S34678");
+    /// Seeds rule (`B2/S`)
+    pub const SEEDS: Self = Self::from_notation("B2/S");
+
+    /// Parses a rule string in **B/S** or **S/B** notation
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleParseError`] if the rule is invalid
+    pub const fn parse(rule: &str) -> Result<Self, RuleParseError> {
+        let bytes = rule.as_bytes();
+        if bytes.is_empty() {
+            return Err(RuleParseError::InvalidFormat);
+        }
+        match bytes[0] {
+            b'B' | b'b' | b'S' | b's' => Self::parse_bs(bytes),
+            _ => Self::parse_sb(bytes),
+        }
+    }
+
+    /// Parses a rule in `B3/S23` notation
+    pub(crate) const fn parse_bs(bytes: &[u8]) -> Result<Self, RuleParseError> {
+        let mut rule = Self {
+            birth: 0,
+            survival: 0,
+        };
+        let (mut birth_seen, mut survival_seen) = (false, false);
+        // `true` for birth, `false` for survival
+        let mut birth_section = true;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'B' | b'b' => {
+                    if birth_seen {
+                        return Err(RuleParseError::DuplicateSection('B'));
+                    }
+                    birth_seen = true;
+                    birth_section = true;
+                }
+                b'S' | b's' => {
+                    if survival_seen {
+                        return Err(RuleParseError::DuplicateSection('S'));
+                    }
+                    survival_seen = true;
+                    birth_section = false;
+                }
+                // A separator must start a new section, digits would otherwise
+                // be added to the previous one
+                b'/' if i > 0 && i + 1 < bytes.len() => {
+                    if !matches!(bytes[i + 1], b'B' | b'b' | b'S' | b's') {
+                        return Err(RuleParseError::InvalidFormat);
+                    }
+                }
+                b'9' => return Err(RuleParseError::OutOfRange),
+                c @ b'0'..=b'8' => {
+                    let bit = 1 << (c - b'0');
+                    if birth_section {
+                        rule.birth |= bit;
+                    } else {
+                        rule.survival |= bit;
+                    }
+                }
+                c => return Err(RuleParseError::InvalidCharacter(c as char)),
+            }
+            i += 1;
+        }
+        Ok(rule)
+    }
+
+    /// Parses a rule in `23/3` notation
+    pub(crate) const fn parse_sb(bytes: &[u8]) -> Result<Self, RuleParseError> {
+        let mut rule = Self {
+            birth: 0,
+            survival: 0,
+        };
+        let mut separator_seen = false;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'/' if separator_seen => return Err(RuleParseError::DuplicateSection('/')),
+                b'/' => separator_seen = true,
+                b'9' => return Err(RuleParseError::OutOfRange),
+                c @ b'0'..=b'8' => {
+                    let bit = 1 << (c - b'0');
+                    if separator_seen {
+                        rule.birth |= bit;
+                    } else {
+                        rule.survival |= bit;
+                    }
+                }
+                c => return Err(RuleParseError::InvalidCharacter(c as char)),
+            }
+            i += 1;
+        }
+        if !separator_seen {
+            return Err(RuleParseError::InvalidFormat);
+        }
+        Ok(rule)
+    }
+
+    /// Parses a rule string in **B/S** or **S/B** notation, meant for
+    /// constant evaluation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn from_notation(rule: &str) -> Self {
+        match Self::parse(rule) {
+            Ok(rule) => rule,
+            Err(_) => panic!("Invalid life-like rule"),
+        }
+    }
+
+    /// Parses a rule string in **B/S** or **S/B** notation and encodes it,
+    /// to be used as a [`LifeLikeCellState`] const argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule is invalid, which fails compilation in a const
+    /// context
+    #[must_use]
+    pub const fn bits(rule: &str) -> u64 {
+        Self::from_notation(rule).to_bits()
+    }
+
+    /// Encodes the rule in a single `u64`
+    #[must_use]
+    #[inline]
+    pub const fn to_bits(self) -> u64 {
+        self.birth as u64 | ((self.survival as u64) << 32)
+    }
+
+    /// Decodes a rule encoded with [`Self::to_bits`]
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self {
+            birth: bits as u32,
+            survival: (bits >> 32) as u32,
+        }
+    }
+
+    /// Is a dead cell with `count` live neighbors born
+    #[must_use]
+    #[inline]
+    pub const fn is_born(&self, count: usize) -> bool {
+        count < 32 && self.birth & (1 << count) != 0
+    }
+
+    /// Does a live cell with `count` live neighbors survive
+    #[must_use]
+    #[inline]
+    pub const fn survives(&self, count: usize) -> bool {
+        count < 32 && self.survival & (1 << count) != 0
+    }
+}
+
+impl FromStr for LifeLikeRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for LifeLikeRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for count in (0..32).filter(|&c| self.is_born(c)) {
+            write!(f, "{count}")?;
+        }
+        write!(f, "/S")?;
+        for count in (0..32).filter(|&c| self.survives(c)) {
+            write!(f, "{count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Generic Life-like cellular automaton state, following the totalistic
+/// [`LifeLikeRule`] encoded in the `RULE` const argument (see
+/// [`LifeLikeRule::bits`]).
+///
+/// A dead cell is `false`, a live cell is `true`
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{LifeLikeCellState, LifeLikeRule};
+/// type MazeCellState = LifeLikeCellState<{ LifeLikeRule::bits("B3/S12345") }>;
+/// ```
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct LifeLikeCellState<const RULE: u64>(pub bool);
+
+/// `HighLife` (`B36/S23`) cell state
+pub type HighLifeCellState = LifeLikeCellState<{ LifeLikeRule::HIGH_LIFE.to_bits() }>;
+/// Day & Night (`B3678/S34678`) cell state
+pub type DayAndNightCellState = LifeLikeCellState<{ LifeLikeRule::DAY_AND_NIGHT.to_bits() }>;
+/// Seeds (`B2/S`) cell state
+pub type SeedsCellState = LifeLikeCellState<{ LifeLikeRule::SEEDS.to_bits() }>;
+
+impl<const RULE: u64> LifeLikeCellState<RULE> {
+    /// The state rule
+    pub const RULE: LifeLikeRule = LifeLikeRule::from_bits(RULE);
+}
+
+impl<const RULE: u64> CellState for LifeLikeCellState<RULE> {
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        let alive_cells_count = neighbor_cells.filter(|&c| c.0).count();
+        let alive = if self.0 {
+            Self::RULE.survives(alive_cells_count)
+        } else {
+            Self::RULE.is_born(alive_cells_count)
+        };
+        Self(alive)
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        if self.0 {
+            Some(Color::WHITE)
+        } else {
+            None
+        }
+    }
+}
+
+impl<const RULE: u64> Deref for LifeLikeCellState<RULE> {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const RULE: u64> DerefMut for LifeLikeCellState<RULE> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const RULE: u64> From<bool> for LifeLikeCellState<RULE> {
+    fn from(val: bool) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConwayCellState;
+
+    #[test]
+    fn parse_notations() {
+        let conway = LifeLikeRule {
+            birth: 0b1000,
+            survival: 0b1100,
+        };
+        assert_eq!(LifeLikeRule::parse("B3/S23"), Ok(conway));
+        assert_eq!(LifeLikeRule::parse("b3s23"), Ok(conway));
+        assert_eq!(LifeLikeRule::parse("S23/B3"), Ok(conway));
+        assert_eq!(LifeLikeRule::parse("23/3"), Ok(conway));
+        assert_eq!(LifeLikeRule::CONWAY, conway);
+        let life_without_death = LifeLikeRule::parse("B3/S012345678").unwrap();
+        assert_eq!(life_without_death.survival, 0b1_1111_1111);
+        assert_eq!(LifeLikeRule::SEEDS.survival, 0);
+        assert_eq!(LifeLikeRule::parse("/2"), Ok(LifeLikeRule::SEEDS));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(LifeLikeRule::parse(""), Err(RuleParseError::InvalidFormat));
+        assert_eq!(
+            LifeLikeRule::parse("23"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            LifeLikeRule::parse("B3/S2a"),
+            Err(RuleParseError::InvalidCharacter('a'))
+        );
+        assert_eq!(
+            LifeLikeRule::parse("B3/B2"),
+            Err(RuleParseError::DuplicateSection('B'))
+        );
+        assert_eq!(
+            LifeLikeRule::parse("23/3/1"),
+            Err(RuleParseError::DuplicateSection('/'))
+        );
+        assert_eq!(
+            LifeLikeRule::parse("B3/23"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            LifeLikeRule::parse("B3//S23"),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(
+            LifeLikeRule::parse("B39/S23"),
+            Err(RuleParseError::OutOfRange)
+        );
+        assert_eq!(
+            LifeLikeRule::parse("B3/S9"),
+            Err(RuleParseError::OutOfRange)
+        );
+        assert_eq!(LifeLikeRule::parse("9/3"), Err(RuleParseError::OutOfRange));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let rule = LifeLikeRule::DAY_AND_NIGHT;
+        assert_eq!(rule.to_string(), "B3678/S34678");
+        assert_eq!(rule.to_string().parse(), Ok(rule));
+        assert_eq!(LifeLikeRule::from_bits(rule.to_bits()), rule);
+    }
+
+    #[test]
+    fn matches_conway() {
+        type State = LifeLikeCellState<{ LifeLikeRule::bits("B3/S23") }>;
+        for alive in [false, true] {
+            for count in 0..=8 {
+                let neighbors: Vec<_> = (0..8).map(|i| State::from(i < count)).collect();
+                let conway_neighbors: Vec<_> = (0..8).map(|i| ConwayCellState(i < count)).collect();
+                assert_eq!(
+                    State::from(alive).new_cell_state(neighbors.iter()).0,
+                    ConwayCellState(alive)
+                        .new_cell_state(conway_neighbors.iter())
+                        .0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn high_life_replicator_birth() {
+        let neighbors: Vec<_> = (0..8).map(|i| HighLifeCellState::from(i < 6)).collect();
+        assert!(
+            HighLifeCellState::from(false)
+                .new_cell_state(neighbors.iter())
+                .0
+        );
+        assert!(
+            !HighLifeCellState::from(true)
+                .new_cell_state(neighbors.iter())
+                .0
+        );
+    }
+}
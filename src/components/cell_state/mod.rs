@@ -1,16 +1,49 @@
-use bevy::prelude::Component;
+use crate::components::Cell;
+use bevy::{ecs::component::Mutable, prelude::Component};
+#[cfg(feature = "2D")]
+pub use block_rule::*;
+pub use context::*;
 pub use conway_state::*;
 pub use conway_state_3d::*;
 pub use cyclic_color_state::*;
+#[cfg(feature = "1D")]
+pub use elementary_state::*;
+pub use forest_fire_state::*;
+pub use generations_state::*;
 pub use immigration_state::*;
+pub use isotropic_state::*;
+pub use larger_than_life_state::*;
+pub use lenia_state::*;
+pub use life_like_state::*;
+pub use probabilistic_life_state::*;
 pub use rainbow_state::*;
+pub use rng::*;
+pub use sandpile_state::*;
+pub use smooth_life_state::*;
+pub use turmite_state::*;
 pub use wire_world_cell_state::*;
 
+#[cfg(feature = "2D")]
+mod block_rule;
+mod context;
 mod conway_state;
 mod conway_state_3d;
 mod cyclic_color_state;
+#[cfg(feature = "1D")]
+mod elementary_state;
+mod forest_fire_state;
+mod generations_state;
 mod immigration_state;
+mod isotropic_state;
+mod larger_than_life_state;
+mod lenia_state;
+mod life_like_state;
+mod probabilistic_life_state;
 mod rainbow_state;
+mod rng;
+mod sandpile_state;
+mod smooth_life_state;
+mod turmite_state;
 mod wire_world_cell_state;
 
 /// This trait defines the state of any given `Cell`. The trait implementation
@@ -18,7 +51,29 @@ mod wire_world_cell_state;
 /// applied.
 ///
 /// Every type defining a `Cell` state and rules must implement this trait.
-pub trait CellState: Component + Sized + Clone + PartialEq {
+pub trait CellState: Component<Mutability = Mutable> + Sized + Clone + PartialEq {
+    /// Are the rules purely neighbor driven, meaning that a cell state may only
+    /// change if its own state or one of its neighbors state changed during
+    /// the previous generation.
+    ///
+    /// If `true` (default), only such *active* cells are evaluated every
+    /// generation, stable areas are never recomputed.
+    /// Set it to `false` for rules which may change a cell state
+    /// spontaneously, every cell will then be evaluated every generation.
+    /// Cells reading the generation, time or randomness of their
+    /// [`CellContext`] are always evaluated in the next generation.
+    const NEIGHBOR_DRIVEN: bool = true;
+
+    /// Do the rules depend on the neighbor weights given by
+    /// [`Cell::neighbor_weights`].
+    ///
+    /// If `true`, the simulation evaluates the cells through
+    /// [`Self::new_cell_state_weighted`] instead of
+    /// [`Self::new_cell_state_positional`]. Defaults to `false`.
+    ///
+    /// [`Cell::neighbor_weights`]: crate::Cell::neighbor_weights
+    const WEIGHTED: bool = false;
+
     /// Defines the new state for a cell given the `neighbor_cells` states and
     /// `self`.
     ///
@@ -26,6 +81,95 @@ pub trait CellState: Component + Sized + Clone + PartialEq {
     #[must_use]
     fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self;
 
+    /// Defines the new state for a cell given the `neighbor_cells` states and
+    /// `self`, for rules depending on the position of the neighbors like
+    /// directional or non-totalistic rules.
+    ///
+    /// The `neighbor_cells` are `(index, state)` pairs in the order defined by
+    /// [`Cell::neighbor_coordinates`], the `index` identifying the neighbor
+    /// direction (see the `NEIGHBOR_OFFSETS` of the provided cells) and the
+    /// `state` being `None` for missing neighbors.
+    ///
+    /// By default it ignores the missing neighbors and calls
+    /// [`Self::new_cell_state`].
+    ///
+    /// [`Cell::neighbor_coordinates`]: crate::Cell::neighbor_coordinates
+    #[must_use]
+    #[inline]
+    fn new_cell_state_positional<'a>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+    ) -> Self {
+        self.new_cell_state(neighbor_cells.filter_map(|(_, state)| state))
+    }
+
+    /// Defines the new state for a cell given the weighted `neighbor_cells`
+    /// states and `self`, for rules consuming weighted sums like Lenia,
+    /// reaction-diffusion or diffusion-limited aggregation.
+    ///
+    /// The `neighbor_cells` are `(weight, state)` pairs in the order defined by
+    /// [`Cell::neighbor_coordinates`], weighted by [`Cell::neighbor_weights`],
+    /// the `state` being `None` for missing neighbors. It is only called by the
+    /// simulation if [`Self::WEIGHTED`] is `true`.
+    ///
+    /// By default it ignores the weights and the missing neighbors and calls
+    /// [`Self::new_cell_state`].
+    ///
+    /// [`Cell::neighbor_coordinates`]: crate::Cell::neighbor_coordinates
+    /// [`Cell::neighbor_weights`]: crate::Cell::neighbor_weights
+    #[must_use]
+    #[inline]
+    fn new_cell_state_weighted<'a>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (f32, Option<&'a Self>)>,
+    ) -> Self {
+        self.new_cell_state(neighbor_cells.filter_map(|(_, state)| state))
+    }
+
+    /// Defines the new state for a cell given the `neighbor_cells` states,
+    /// `self` and the evaluation `context`, for rules depending on the cell
+    /// position, the generation or the time.
+    ///
+    /// The `neighbor_cells` are the same as in
+    /// [`Self::new_cell_state_positional`].
+    ///
+    /// By default it calls [`Self::new_cell_state_weighted`] with the
+    /// [`CellContext::neighbor_weight`] of every neighbor if [`Self::WEIGHTED`]
+    /// is `true`, or
+    /// [`Self::new_cell_state_positional`] otherwise. It is the method called
+    /// by the simulation.
+    ///
+    /// Reading the [`CellContext::generation`], [`CellContext::delta`] or
+    /// [`CellContext::rng`] keeps the cell evaluated in the next generation,
+    /// even for [`Self::NEIGHBOR_DRIVEN`] rules and stable neighborhoods.
+    #[must_use]
+    #[inline]
+    fn new_cell_state_with_context<'a, C: Cell>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+        context: &CellContext<C>,
+    ) -> Self {
+        if Self::WEIGHTED {
+            self.new_cell_state_weighted(
+                neighbor_cells.map(|(i, state)| (context.neighbor_weight(i), state)),
+            )
+        } else {
+            self.new_cell_state_positional(neighbor_cells)
+        }
+    }
+
+    /// Checks that the rules can be simulated on `C` cells, called once when a
+    /// simulation plugin of `C` cells with this state is built.
+    ///
+    /// Does nothing by default.
+    ///
+    /// # Panics
+    ///
+    /// Implementations panic if the `C` cells don't match the rules, like a
+    /// neighborhood range different from the rule range
+    #[inline]
+    fn check_cell_type<C: Cell>() {}
+
     #[cfg(feature = "auto-coloring")]
     /// Color of the state, to use with `auto-coloring` feature
     #[must_use]
@@ -0,0 +1,130 @@
+use crate::components::{Cell, CellContext, CellState, LifeLikeRule};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+
+/// Probabilistic Life-like cellular automaton state, where births and deaths
+/// only happen with a given probability.
+///
+/// The state follows the [`LifeLikeRule`] encoded in the `RULE` const argument
+/// (see [`LifeLikeRule::bits`]), every birth and death happens with a
+/// `PROBABILITY` given in millionths. A cell whose state should change
+/// according to the rule keeps its state otherwise. The random values are drawn
+/// from [`CellContext::rng`], the simulation can be seeded with the
+/// [`SimulationRng`](crate::SimulationRng) resource.
+///
+/// A dead cell is `false`, a live cell is `true`
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{LifeLikeRule, ProbabilisticLifeCellState};
+/// // Conway's game of life where transitions happen 90% of the time
+/// type State = ProbabilisticLifeCellState<{ LifeLikeRule::CONWAY.to_bits() }, 900_000>;
+/// assert_eq!(State::PROBABILITY, 0.9);
+/// ```
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct ProbabilisticLifeCellState<const RULE: u64, const PROBABILITY: u32>(pub bool);
+
+/// Conway's game of life (`B3/S23`) cell state where transitions happen half
+/// of the time
+pub type ProbabilisticConwayCellState =
+    ProbabilisticLifeCellState<{ LifeLikeRule::CONWAY.to_bits() }, 500_000>;
+
+impl<const RULE: u64, const PROBABILITY: u32> ProbabilisticLifeCellState<RULE, PROBABILITY> {
+    /// The state rule
+    pub const RULE: LifeLikeRule = LifeLikeRule::from_bits(RULE);
+    /// Probability for a birth or a death to happen
+    #[allow(clippy::cast_lossless)]
+    pub const PROBABILITY: f64 = PROBABILITY as f64 / 1_000_000.0;
+}
+
+impl<const RULE: u64, const PROBABILITY: u32> CellState
+    for ProbabilisticLifeCellState<RULE, PROBABILITY>
+{
+    /// Cells may keep a state the rule would change, every cell must be
+    /// evaluated
+    const NEIGHBOR_DRIVEN: bool = false;
+
+    /// Deterministic evaluation, every transition happens
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        let alive_cells_count = neighbor_cells.filter(|&c| c.0).count();
+        let alive = if self.0 {
+            Self::RULE.survives(alive_cells_count)
+        } else {
+            Self::RULE.is_born(alive_cells_count)
+        };
+        Self(alive)
+    }
+
+    fn new_cell_state_with_context<'a, C: Cell>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+        context: &CellContext<C>,
+    ) -> Self {
+        let state = self.new_cell_state(neighbor_cells.filter_map(|(_, state)| state));
+        if state != *self && context.rng().chance(Self::PROBABILITY) {
+            state
+        } else {
+            *self
+        }
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        if self.0 {
+            Some(Color::WHITE)
+        } else {
+            None
+        }
+    }
+}
+
+impl<const RULE: u64, const PROBABILITY: u32> From<bool>
+    for ProbabilisticLifeCellState<RULE, PROBABILITY>
+{
+    fn from(val: bool) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(all(test, feature = "2D"))]
+mod tests {
+    use super::*;
+    use crate::MooreCell2d;
+    use bevy::math::IVec2;
+    use std::time::Duration;
+
+    type State = ProbabilisticLifeCellState<{ LifeLikeRule::CONWAY.to_bits() }, 300_000>;
+
+    #[test]
+    fn transition_probability() {
+        let alive = State::from(true);
+        let dead = State::from(false);
+        let mut births = 0;
+        let mut survivals = 0;
+        for x in 0..100 {
+            for y in 0..100 {
+                let cell = MooreCell2d::new(IVec2::new(x, y));
+                let context = CellContext::new(&cell, 0, Duration::ZERO).with_seed(7);
+                let neighbors = [alive; 3];
+                let state = dead
+                    .new_cell_state_with_context(neighbors.iter().map(Some).enumerate(), &context);
+                births += usize::from(state.0);
+                // Stable cells never change
+                let state = alive
+                    .new_cell_state_with_context(neighbors.iter().map(Some).enumerate(), &context);
+                assert!(state.0);
+                let neighbors = [alive; 4];
+                let state = alive
+                    .new_cell_state_with_context(neighbors.iter().map(Some).enumerate(), &context);
+                survivals += usize::from(state.0);
+            }
+        }
+        // 3000 births and 7000 survivals expected, the standard deviation is
+        // about 46
+        assert!((2_800..3_200).contains(&births), "{births} births");
+        assert!((6_800..7_200).contains(&survivals), "{survivals} survivals");
+    }
+}
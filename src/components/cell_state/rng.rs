@@ -0,0 +1,131 @@
+use bevy::platform::hash::FixedHasher;
+use std::hash::{BuildHasher, Hash};
+
+/// Increment of the `SplitMix64` generator
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// `SplitMix64` output function, mixing the bits of `z`
+const fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Deterministic random number stream of a cell for a single generation,
+/// retrieved through [`CellContext::rng`](crate::CellContext::rng).
+///
+/// The stream is derived from the [`SimulationRng`](crate::SimulationRng)
+/// seed, the cell coordinates and the generation, so a stochastic simulation
+/// is reproducible whatever the evaluation order of the cells.
+///
+/// The generator is a `SplitMix64`, fast but not cryptographically secure.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::CellRng;
+/// let mut rng = CellRng::new(42);
+/// let value = rng.next_f32();
+/// assert!((0.0..1.0).contains(&value));
+/// assert!(rng.below(6) < 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellRng {
+    state: u64,
+}
+
+impl CellRng {
+    /// Instantiates a new stream from the given `seed`
+    #[must_use]
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Instantiates the stream of the cell at `coords` for the given
+    /// `generation`
+    #[must_use]
+    pub fn from_cell<H: Hash>(seed: u64, coords: &H, generation: u64) -> Self {
+        let hash = FixedHasher.hash_one(coords);
+        Self::new(mix(
+            seed ^ mix(hash ^ mix(generation.wrapping_add(GOLDEN_GAMMA)))
+        ))
+    }
+
+    /// Retrieves the next random `u64`
+    #[inline]
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        mix(self.state)
+    }
+
+    /// Retrieves the next random `u32`
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Retrieves the next random `f32` in the `[0, 1)` range
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1_u32 << 24) as f32
+    }
+
+    /// Retrieves the next random `f64` in the `[0, 1)` range
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Retrieves the next random `u64` in the `[0, bound)` range, `bound`
+    /// must not be zero
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// Returns `true` with the given `probability`, between 0 and 1
+    #[inline]
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_streams() {
+        let mut a = CellRng::from_cell(7, &(1, 2), 3);
+        let mut b = CellRng::from_cell(7, &(1, 2), 3);
+        for _ in 0..16 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        let first = CellRng::from_cell(7, &(1, 2), 3).next_u64();
+        assert_ne!(first, CellRng::from_cell(8, &(1, 2), 3).next_u64());
+        assert_ne!(first, CellRng::from_cell(7, &(2, 1), 3).next_u64());
+        assert_ne!(first, CellRng::from_cell(7, &(1, 2), 4).next_u64());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = CellRng::new(0);
+        let mut hits = 0;
+        for _ in 0..10_000 {
+            assert!((0.0..1.0).contains(&rng.next_f32()));
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+            assert!(rng.below(3) < 3);
+            if rng.chance(0.25) {
+                hits += 1;
+            }
+        }
+        assert!((2_000..3_000).contains(&hits));
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::components::{Cell, CellContext, CellState};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+use std::ops::{Deref, DerefMut};
+
+/// [Abelian sandpile] state, the inner value being the amount of grains of the
+/// cell.
+///
+/// A cell holding at least as many grains as it has neighbors is unstable and
+/// *topples*: it gives one grain to each of its neighbors. Grains given to
+/// missing neighbors fall off the edge of the pile. The threshold is `4` for a
+/// `NeumannCell2d`, `6` for a `HexagonCell2d` and `8` for a `MooreCell2d`.
+///
+/// The state can be simulated by a `CellularAutomatonPlugin`, but the
+/// [`SandpilePlugin`](crate::SandpilePlugin) adds grain dropping,
+/// relaxation to stability and avalanche statistics.
+///
+/// [Abelian sandpile]: https://en.wikipedia.org/wiki/Abelian_sandpile_model
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct SandpileCellState(pub u32);
+
+impl SandpileCellState {
+    /// Is the cell unstable for the given toppling `threshold`, its neighbor
+    /// count
+    #[must_use]
+    #[inline]
+    pub const fn is_unstable(self, threshold: u32) -> bool {
+        threshold > 0 && self.0 >= threshold
+    }
+
+    /// Toppling threshold of `cell`, its neighbor count including missing
+    /// neighbors
+    #[must_use]
+    #[inline]
+    pub fn threshold<C: Cell>(cell: &C) -> u32 {
+        u32::try_from(cell.neighbor_coordinates().len()).unwrap_or(u32::MAX)
+    }
+
+    /// Computes the next state for the given toppling `threshold`, with the
+    /// grains `received` from the unstable neighbors. An unstable cell gives
+    /// away `threshold` grains.
+    #[must_use]
+    #[inline]
+    pub const fn topple(self, threshold: u32, received: u32) -> Self {
+        let kept = if self.is_unstable(threshold) {
+            self.0 - threshold
+        } else {
+            self.0
+        };
+        Self(kept.saturating_add(received))
+    }
+
+    /// Computes the next state, the toppling threshold being the amount of
+    /// `neighbors`, including missing ones
+    fn topple_neighbors<'a>(self, neighbors: impl Iterator<Item = Option<&'a Self>>) -> Self {
+        let neighbors: Vec<_> = neighbors.collect();
+        let threshold = u32::try_from(neighbors.len()).unwrap_or(u32::MAX);
+        let received = neighbors
+            .into_iter()
+            .flatten()
+            .filter(|c| c.is_unstable(threshold))
+            .count();
+        self.topple(threshold, u32::try_from(received).unwrap_or(u32::MAX))
+    }
+}
+
+impl CellState for SandpileCellState {
+    /// The toppling threshold is the amount of given neighbors
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        self.topple_neighbors(neighbor_cells.map(Some))
+    }
+
+    /// Missing neighbors count in the toppling threshold
+    fn new_cell_state_positional<'a>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+    ) -> Self {
+        self.topple_neighbors(neighbor_cells.map(|(_, state)| state))
+    }
+
+    /// The toppling threshold is the neighbor count of the evaluated cell, see
+    /// [`Self::threshold`]
+    fn new_cell_state_with_context<'a, C: Cell>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+        context: &CellContext<C>,
+    ) -> Self {
+        let threshold = Self::threshold(context.cell());
+        let received = neighbor_cells
+            .filter(|(_, state)| state.is_some_and(|c| c.is_unstable(threshold)))
+            .count();
+        self.topple(threshold, u32::try_from(received).unwrap_or(u32::MAX))
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        match self.0 {
+            0 => None,
+            1 => Some(Color::srgb(0.2, 0.4, 1.0)),
+            2 => Some(Color::srgb(0.3, 0.8, 0.3)),
+            3 => Some(Color::srgb(1.0, 0.8, 0.2)),
+            _ => Some(Color::srgb(1.0, 0.2, 0.2)),
+        }
+    }
+}
+
+impl Deref for SandpileCellState {
+    type Target = u32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SandpileCellState {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<u32> for SandpileCellState {
+    fn from(val: u32) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toppling() {
+        let state = |cell: u32, neighbors: [Option<u32>; 4]| {
+            let neighbors = neighbors.map(|n| n.map(SandpileCellState));
+            SandpileCellState(cell)
+                .new_cell_state_positional(neighbors.iter().map(Option::as_ref).enumerate())
+                .0
+        };
+        // Stable
+        assert_eq!(state(3, [Some(0); 4]), 3);
+        // Topples, even with missing neighbors
+        assert_eq!(state(4, [Some(0), None, None, None]), 0);
+        assert_eq!(state(5, [Some(0); 4]), 1);
+        // Receives grains from unstable neighbors
+        assert_eq!(state(2, [Some(4), Some(3), Some(7), None]), 4);
+        assert_eq!(state(4, [Some(4); 4]), 4);
+        // Without positions, the threshold is the amount of given neighbors
+        let neighbors = [SandpileCellState(2), SandpileCellState(1)];
+        assert_eq!(SandpileCellState(2).new_cell_state(neighbors.iter()).0, 1);
+        // Even without a size hint
+        let mut neighbors = neighbors.iter();
+        let neighbors = std::iter::from_fn(|| neighbors.next());
+        assert_eq!(SandpileCellState(2).new_cell_state(neighbors).0, 1);
+    }
+}
@@ -0,0 +1,367 @@
+use crate::components::{
+    Cell, CellContext, CellState, ContinuousColoring, Neighborhood, NeighborhoodShape,
+};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::{Component, IVec2};
+use std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
+
+/// `SmoothLife` rule parameters, to use with a [`SmoothLifeCellState`].
+///
+/// The neighborhood is split between an inner disk of [`Self::INNER_RADIUS`],
+/// including the center cell, and an outer ring up to [`Self::OUTER_RADIUS`].
+/// The average value of the disk defines if the cell is *alive* and the
+/// average value of the ring is the smooth equivalent of the live neighbor
+/// count.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{SmoothLifeCellState, SmoothLifeRule};
+/// /// `SmoothLife` rule with a smaller neighborhood
+/// struct SmallSmoothLifeRule;
+///
+/// impl SmoothLifeRule for SmallSmoothLifeRule {
+///     const OUTER_RADIUS: u8 = 12;
+///     const INNER_RADIUS: f32 = 4.0;
+///     const BIRTH: (f32, f32) = (0.278, 0.365);
+///     const SURVIVAL: (f32, f32) = (0.267, 0.445);
+///     const ALPHA_N: f32 = 0.028;
+///     const ALPHA_M: f32 = 0.147;
+///     const TIME_STEP: f32 = 0.1;
+/// }
+///
+/// type SmallSmoothLifeCellState = SmoothLifeCellState<SmallSmoothLifeRule>;
+/// ```
+pub trait SmoothLifeRule: Send + Sync + 'static {
+    /// The outer ring radius, in cells, must match the cell range
+    const OUTER_RADIUS: u8;
+    /// The inner disk radius, usually a third of the outer radius
+    const INNER_RADIUS: f32;
+    /// The interval of outer ring averages giving birth to a dead cell
+    const BIRTH: (f32, f32);
+    /// The interval of outer ring averages allowing a live cell to survive
+    const SURVIVAL: (f32, f32);
+    /// The smoothness of the birth and survival interval edges
+    const ALPHA_N: f32;
+    /// The smoothness of the transition between dead and live cells
+    const ALPHA_M: f32;
+    /// The fraction of the transition applied every generation, `1.0` for
+    /// discrete time steps
+    const TIME_STEP: f32;
+    /// The neighborhood shape of the cell, defaults to
+    /// [`NeighborhoodShape::Circular`]. The outer ring is made of every
+    /// neighbor outside of the inner disk.
+    const NEIGHBORHOOD: NeighborhoodShape = NeighborhoodShape::Circular;
+    /// The state coloring, defaults to [`ContinuousColoring::Grayscale`]
+    const COLORING: ContinuousColoring = ContinuousColoring::Grayscale;
+
+    /// Transition function, giving the target value of a cell for the inner
+    /// disk average `m` and the outer ring average `n`
+    #[must_use]
+    fn transition(n: f32, m: f32) -> f32 {
+        let sigmoid = |x: f32, a: f32, alpha: f32| 1.0 / (1.0 + (-(x - a) * 4.0 / alpha).exp());
+        let aliveness = sigmoid(m, 0.5, Self::ALPHA_M);
+        let threshold =
+            |birth: f32, survival: f32| birth.mul_add(1.0 - aliveness, survival * aliveness);
+        let low = threshold(Self::BIRTH.0, Self::SURVIVAL.0);
+        let high = threshold(Self::BIRTH.1, Self::SURVIVAL.1);
+        sigmoid(n, low, Self::ALPHA_N) * (1.0 - sigmoid(n, high, Self::ALPHA_N))
+    }
+}
+
+/// [`Neighborhood`] of the `R` [`SmoothLifeRule`], see the
+/// [`SmoothLifeRangeCell2d`](crate::SmoothLifeRangeCell2d) cells.
+///
+/// Neighbors within the inner disk have a `1.0` weight, the outer ring
+/// neighbors have a `0.0` weight.
+///
+/// # Panics
+///
+/// Computing the weights panics if the range doesn't match the outer radius
+pub struct SmoothLifeNeighborhood<R>(PhantomData<R>);
+
+impl<R: SmoothLifeRule> Neighborhood for SmoothLifeNeighborhood<R> {
+    const SHAPE: NeighborhoodShape = R::NEIGHBORHOOD;
+
+    fn weight(offset: IVec2, _range: i32) -> f32 {
+        if offset.as_vec2().length() <= R::INNER_RADIUS {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn weights(range: i32) -> Vec<f32> {
+        assert_eq!(
+            range,
+            i32::from(R::OUTER_RADIUS),
+            "The cell range must match the `{}` outer radius",
+            std::any::type_name::<R>(),
+        );
+        Self::SHAPE
+            .offsets(range)
+            .map(|offset| Self::weight(offset, range))
+            .collect()
+    }
+}
+
+impl<R> Debug for SmoothLifeNeighborhood<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("SmoothLifeNeighborhood")
+    }
+}
+
+impl<R> Clone for SmoothLifeNeighborhood<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for SmoothLifeNeighborhood<R> {}
+
+impl<R> Default for SmoothLifeNeighborhood<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Classic `SmoothLife` rule parameters from Stephan Rafler's paper
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct DefaultSmoothLifeRule;
+
+impl SmoothLifeRule for DefaultSmoothLifeRule {
+    const OUTER_RADIUS: u8 = 21;
+    const INNER_RADIUS: f32 = 7.0;
+    const BIRTH: (f32, f32) = (0.278, 0.365);
+    const SURVIVAL: (f32, f32) = (0.267, 0.445);
+    const ALPHA_N: f32 = 0.028;
+    const ALPHA_M: f32 = 0.147;
+    const TIME_STEP: f32 = 0.1;
+}
+
+/// [`SmoothLife`](https://arxiv.org/abs/1111.1567) continuous cellular
+/// automaton state, a continuous generalization of Conway's game of life
+/// following the `R` [`SmoothLifeRule`].
+///
+/// The state value is between `0.0` and `1.0`. Every generation the cell
+/// value moves toward the [`SmoothLifeRule::transition`] target, by the rule
+/// time step. Missing neighbors count as `0.0` values.
+///
+/// This state must be used with cells weighted by the
+/// [`SmoothLifeNeighborhood`] of the rule, with a range matching the outer
+/// radius, like
+/// [`SmoothLifeRangeCell2d<DefaultSmoothLifeRule, 21>`](crate::SmoothLifeRangeCell2d)
+/// for the [`DefaultSmoothLifeRule`]. Building a simulation plugin panics with
+/// other cells.
+#[derive(Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct SmoothLifeCellState<R: SmoothLifeRule> {
+    /// The cell value, between `0.0` and `1.0`
+    pub value: f32,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    phantom: PhantomData<R>,
+}
+
+/// Classic `SmoothLife` cell state
+pub type DefaultSmoothLifeCellState = SmoothLifeCellState<DefaultSmoothLifeRule>;
+
+impl<R: SmoothLifeRule> SmoothLifeCellState<R> {
+    /// The dead state
+    pub const DEAD: Self = Self::new(0.0);
+    /// The fully alive state
+    pub const ALIVE: Self = Self::new(1.0);
+
+    /// Instantiates a new state with the given `value`, clamped between `0.0`
+    /// and `1.0`
+    #[must_use]
+    #[inline]
+    pub const fn new(value: f32) -> Self {
+        Self {
+            value: value.clamp(0.0, 1.0),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Computes the next state for the inner disk average `m` and the outer
+    /// ring average `n`
+    fn next(self, n: f32, m: f32) -> Self {
+        let growth = 2.0f32.mul_add(R::transition(n, m), -1.0);
+        Self::new(R::TIME_STEP.mul_add(growth, self.value))
+    }
+}
+
+impl<R: SmoothLifeRule> CellState for SmoothLifeCellState<R> {
+    const WEIGHTED: bool = true;
+
+    /// Neighbors without their weight are all considered in the outer ring
+    fn new_cell_state<'a>(&self, neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        let (sum, count) = neighbor_cells.fold((0.0, 0_u16), |(sum, count), c| {
+            (sum + c.value, count.saturating_add(1))
+        });
+        let n = if count == 0 {
+            0.0
+        } else {
+            sum / f32::from(count)
+        };
+        self.next(n, self.value)
+    }
+
+    /// The weights are the [`SmoothLifeNeighborhood`] inner disk weights, the
+    /// center cell belongs to the inner disk
+    fn new_cell_state_weighted<'a>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (f32, Option<&'a Self>)>,
+    ) -> Self {
+        // (sum, weight) of the inner disk and the outer ring
+        let ((inner_sum, inner_weight), (outer_sum, outer_weight)) = neighbor_cells.fold(
+            ((self.value, 1.0), (0.0, 0.0)),
+            |((inner_sum, inner_weight), (outer_sum, outer_weight)), (weight, state)| {
+                let value = state.map_or(0.0, |c| c.value);
+                let outer = 1.0 - weight;
+                (
+                    (weight.mul_add(value, inner_sum), inner_weight + weight),
+                    (outer.mul_add(value, outer_sum), outer_weight + outer),
+                )
+            },
+        );
+        let m = inner_sum / inner_weight;
+        let n = if outer_weight > 0.0 {
+            outer_sum / outer_weight
+        } else {
+            0.0
+        };
+        self.next(n, m)
+    }
+
+    fn new_cell_state_with_context<'a, C: Cell>(
+        &self,
+        neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+        context: &CellContext<C>,
+    ) -> Self {
+        self.new_cell_state_weighted(
+            neighbor_cells.map(|(i, state)| (context.neighbor_weight(i), state)),
+        )
+    }
+
+    fn check_cell_type<C: Cell>() {
+        let disks = SmoothLifeNeighborhood::<R>::weights(i32::from(R::OUTER_RADIUS));
+        assert!(
+            C::neighbor_weights().is_some_and(|weights| weights == disks),
+            "`SmoothLifeCellState` requires cells weighted by the `{}` disks, like `SmoothLifeRangeCell2d`, not `{}` cells",
+            std::any::type_name::<R>(),
+            std::any::type_name::<C>(),
+        );
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        R::COLORING.color(self.value)
+    }
+}
+
+impl<R: SmoothLifeRule> Debug for SmoothLifeCellState<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmoothLifeCellState")
+            .field(&self.value)
+            .finish()
+    }
+}
+
+impl<R: SmoothLifeRule> Clone for SmoothLifeCellState<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: SmoothLifeRule> Copy for SmoothLifeCellState<R> {}
+
+impl<R: SmoothLifeRule> PartialEq for SmoothLifeCellState<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<R: SmoothLifeRule> Default for SmoothLifeCellState<R> {
+    fn default() -> Self {
+        Self::DEAD
+    }
+}
+
+impl<R: SmoothLifeRule> From<f32> for SmoothLifeCellState<R> {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type State = DefaultSmoothLifeCellState;
+
+    #[test]
+    fn transition() {
+        let transition = DefaultSmoothLifeRule::transition;
+        // Birth
+        assert!(transition(0.32, 0.0) > 0.99);
+        assert!(transition(0.2, 0.0) < 0.01);
+        assert!(transition(0.42, 0.0) < 0.01);
+        // Survival
+        assert!(transition(0.35, 1.0) > 0.99);
+        assert!(transition(0.2, 1.0) < 0.01);
+        assert!(transition(0.5, 1.0) < 0.01);
+    }
+
+    #[test]
+    fn inner_disk_weights() {
+        let weights = SmoothLifeNeighborhood::<DefaultSmoothLifeRule>::weights(21);
+        assert_eq!(
+            weights.len(),
+            NeighborhoodShape::Circular.neighbor_count(21)
+        );
+        let inner = weights.iter().filter(|&&w| w > 0.0).count();
+        assert_eq!(inner, NeighborhoodShape::Circular.neighbor_count(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "must match")]
+    fn mismatched_outer_radius() {
+        let _ = SmoothLifeNeighborhood::<DefaultSmoothLifeRule>::weights(13);
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    fn check_cell_type() {
+        State::check_cell_type::<crate::SmoothLifeRangeCell2d<DefaultSmoothLifeRule, 21>>();
+    }
+
+    #[test]
+    #[cfg(feature = "2D")]
+    #[should_panic(expected = "`SmoothLifeCellState` requires cells weighted by the")]
+    fn unweighted_plugin() {
+        bevy::prelude::App::new()
+            .add_plugins(crate::CellularAutomatonPlugin::<crate::MooreCell2d, State>::default());
+    }
+
+    #[test]
+    fn smooth_life_rule() {
+        let weights = SmoothLifeNeighborhood::<DefaultSmoothLifeRule>::weights(21);
+        let state = |cell: f32, neighbor: f32| {
+            let neighbor = State::new(neighbor);
+            let neighbors = weights.iter().map(|&w| (w, Some(&neighbor)));
+            State::new(cell).new_cell_state_weighted(neighbors)
+        };
+        // Empty areas stay empty
+        assert_eq!(state(0.0, 0.0), State::DEAD);
+        // Birth
+        assert!(state(0.0, 0.32).value > 0.09);
+        // Overcrowded neighborhood
+        assert!(state(1.0, 1.0).value < 0.91);
+        assert_eq!(state(0.05, 1.0), State::DEAD);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::components::{CellState, ConwayCellState};
+#[cfg(feature = "auto-coloring")]
+use bevy::prelude::Color;
+use bevy::prelude::Component;
+use std::ops::{Deref, DerefMut};
+
+/// Cell state which can be read and painted by a
+/// [`Turmite`](crate::Turmite), as a color index.
+pub trait TurmiteState: CellState {
+    /// The color index of the state, read by the turmites
+    #[must_use]
+    fn turmite_color(&self) -> u8;
+
+    /// The state painted by a turmite for the given `color` index
+    #[must_use]
+    fn from_turmite_color(color: u8) -> Self;
+}
+
+/// Cell state for [turmites](crate::Turmite) like Langton's ant, the inner
+/// value being the cell color index.
+///
+/// The cells have no rules of their own: their state only changes when painted
+/// by a turmite. The `0` color is hidden.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct TurmiteCellState(pub u8);
+
+impl CellState for TurmiteCellState {
+    fn new_cell_state<'a>(&self, _neighbor_cells: impl Iterator<Item = &'a Self>) -> Self {
+        *self
+    }
+
+    #[cfg(feature = "auto-coloring")]
+    fn color(&self) -> Option<Color> {
+        match self.0 {
+            0 => None,
+            1 => Some(Color::WHITE),
+            c => Some(Color::hsl(f32::from(c - 2) * 47.0 % 360.0, 0.8, 0.5)),
+        }
+    }
+}
+
+impl TurmiteState for TurmiteCellState {
+    #[inline]
+    fn turmite_color(&self) -> u8 {
+        self.0
+    }
+
+    #[inline]
+    fn from_turmite_color(color: u8) -> Self {
+        Self(color)
+    }
+}
+
+impl Deref for TurmiteCellState {
+    type Target = u8;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for TurmiteCellState {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<u8> for TurmiteCellState {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+/// Dead cells are the `0` color and live cells the `1` color, any other color
+/// paints a live cell
+impl TurmiteState for ConwayCellState {
+    #[inline]
+    fn turmite_color(&self) -> u8 {
+        u8::from(self.0)
+    }
+
+    #[inline]
+    fn from_turmite_color(color: u8) -> Self {
+        Self(color != 0)
+    }
+}
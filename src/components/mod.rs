@@ -1,5 +1,7 @@
 pub use cell::*;
 pub use cell_state::*;
+pub use turmite::*;
 
 mod cell;
 mod cell_state;
+mod turmite;
@@ -0,0 +1,406 @@
+use crate::components::{Cell, RuleParseError};
+#[cfg(feature = "2D")]
+use crate::components::{HexagonCell2d, MooreCell2d, NeumannCell2d};
+use bevy::prelude::Component;
+use std::{marker::PhantomData, str::FromStr};
+
+/// Relative turn of a [`Turmite`], applied to its heading before moving
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum Turn {
+    /// Keep going forward (`N`)
+    #[default]
+    None,
+    /// Turn left by one heading (`L` or `L1`)
+    Left,
+    /// Turn right by one heading (`R` or `R1`)
+    Right,
+    /// Turn back (`U`)
+    UTurn,
+    /// Turn left by two headings (`L2`), mostly used on hexagonal grids
+    Left2,
+    /// Turn right by two headings (`R2`), mostly used on hexagonal grids
+    Right2,
+}
+
+impl Turn {
+    /// Applies the turn to `heading`, for a cell with `headings` directions
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn apply(self, heading: u8, headings: u8) -> u8 {
+        let headings = if headings == 0 { 1 } else { headings as u16 };
+        let offset = match self {
+            Self::None => 0,
+            Self::Left => headings - 1,
+            Self::Right => 1,
+            Self::UTurn => headings / 2,
+            Self::Left2 => 2 * headings - 2,
+            Self::Right2 => 2,
+        };
+        ((heading as u16 + offset) % headings) as u8
+    }
+}
+
+/// Transition of a [`Turmite`] for a given internal state and cell color
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct TurmiteTransition {
+    /// The color written on the current cell
+    pub color: u8,
+    /// The turn applied before moving
+    pub turn: Turn,
+    /// The next internal state of the turmite
+    pub state: u8,
+}
+
+impl TurmiteTransition {
+    /// Instantiates a new transition
+    #[must_use]
+    #[inline]
+    pub const fn new(color: u8, turn: Turn, state: u8) -> Self {
+        Self { color, turn, state }
+    }
+}
+
+/// [Turmite] transition table, giving for every internal state and current
+/// cell color the color to write, the turn to make and the next internal state.
+///
+/// Langton's ants, single state turmites, are parsed from the usual turn
+/// strings like `RL` or `RRLLLRLLLRRR`: the `n`th character is the turn made
+/// on a cell of color `n`, which is then painted with the next color. Turns are
+/// `L`, `R`, `N` (no turn) and `U` (u-turn), `L1`, `L2`, `R1` and `R2` are
+/// supported for hexagonal ants.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_life::{Turn, TurmiteRule, TurmiteTransition};
+/// // Langton's ant
+/// let rule: TurmiteRule = "RL".parse().unwrap();
+/// assert_eq!(rule.colors(), 2);
+/// assert_eq!(rule.transition(0, 0), TurmiteTransition::new(1, Turn::Right, 0));
+/// // Two state turmite
+/// let rule = TurmiteRule::new(vec![
+///     vec![
+///         TurmiteTransition::new(1, Turn::Left, 1),
+///         TurmiteTransition::new(1, Turn::Right, 1),
+///     ],
+///     vec![
+///         TurmiteTransition::new(0, Turn::None, 0),
+///         TurmiteTransition::new(0, Turn::None, 1),
+///     ],
+/// ])
+/// .unwrap();
+/// assert_eq!(rule.states(), 2);
+/// ```
+///
+/// [Turmite]: https://en.wikipedia.org/wiki/Turmite
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct TurmiteRule {
+    colors: u8,
+    /// Transitions indexed by `state * colors + color`
+    transitions: Vec<TurmiteTransition>,
+}
+
+impl TurmiteRule {
+    /// Langton's ant rule (`RL`)
+    #[must_use]
+    pub fn langtons_ant() -> Self {
+        Self::ant(&[Turn::Right, Turn::Left])
+    }
+
+    /// Instantiates a single state turmite, turning by `turns[n]` on a cell of
+    /// color `n` and painting it with the next color
+    ///
+    /// # Panics
+    ///
+    /// Panics if `turns` is empty or has more than 255 entries
+    #[must_use]
+    pub fn ant(turns: &[Turn]) -> Self {
+        let colors = u8::try_from(turns.len()).ok().filter(|&c| c > 0);
+        let colors = colors.expect("An ant rule requires between 1 and 255 turns");
+        let transitions = turns
+            .iter()
+            .zip((1..colors).chain([0]))
+            .map(|(&turn, color)| TurmiteTransition::new(color, turn, 0))
+            .collect();
+        Self {
+            colors,
+            transitions,
+        }
+    }
+
+    /// Instantiates a turmite from its transition `table`, every row being an
+    /// internal state and every column a cell color.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleParseError`] if the table is empty, if its rows have
+    /// different lengths or if a transition refers to a missing state or color
+    pub fn new(table: Vec<Vec<TurmiteTransition>>) -> Result<Self, RuleParseError> {
+        let colors = table.first().map_or(0, Vec::len);
+        if colors == 0 || table.iter().any(|row| row.len() != colors) {
+            return Err(RuleParseError::InvalidFormat);
+        }
+        let states = u8::try_from(table.len()).map_err(|_| RuleParseError::OutOfRange)?;
+        let colors = u8::try_from(colors).map_err(|_| RuleParseError::OutOfRange)?;
+        let transitions: Vec<_> = table.into_iter().flatten().collect();
+        if transitions
+            .iter()
+            .any(|t| t.color >= colors || t.state >= states)
+        {
+            return Err(RuleParseError::OutOfRange);
+        }
+        Ok(Self {
+            colors,
+            transitions,
+        })
+    }
+
+    /// Parses a single state turmite from a turn string like `RL`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleParseError`] if the rule is invalid
+    pub fn parse(rule: &str) -> Result<Self, RuleParseError> {
+        let mut turns = Vec::new();
+        let mut chars = rule.chars().peekable();
+        while let Some(c) = chars.next() {
+            let turn = match c.to_ascii_uppercase() {
+                'N' => Turn::None,
+                'U' => Turn::UTurn,
+                'L' | 'R' => {
+                    let left = c.eq_ignore_ascii_case(&'L');
+                    let amount = chars.next_if(char::is_ascii_digit);
+                    match (left, amount) {
+                        (true, None | Some('1')) => Turn::Left,
+                        (false, None | Some('1')) => Turn::Right,
+                        (true, Some('2')) => Turn::Left2,
+                        (false, Some('2')) => Turn::Right2,
+                        _ => return Err(RuleParseError::OutOfRange),
+                    }
+                }
+                _ => return Err(RuleParseError::InvalidCharacter(c)),
+            };
+            turns.push(turn);
+        }
+        match turns.len() {
+            0 => Err(RuleParseError::InvalidFormat),
+            1..=255 => Ok(Self::ant(&turns)),
+            _ => Err(RuleParseError::OutOfRange),
+        }
+    }
+
+    /// Amount of cell colors
+    #[must_use]
+    #[inline]
+    pub const fn colors(&self) -> u8 {
+        self.colors
+    }
+
+    /// Amount of internal states
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn states(&self) -> u8 {
+        (self.transitions.len() / usize::from(self.colors)) as u8
+    }
+
+    /// Transition for the internal `state` on a cell of the given `color`.
+    ///
+    /// Out of range states and colors wrap around
+    #[must_use]
+    pub fn transition(&self, state: u8, color: u8) -> TurmiteTransition {
+        let state = usize::from(state % self.states());
+        let color = usize::from(color % self.colors);
+        self.transitions[state * usize::from(self.colors) + color]
+    }
+}
+
+impl Default for TurmiteRule {
+    fn default() -> Self {
+        Self::langtons_ant()
+    }
+}
+
+impl FromStr for TurmiteRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Cell type a [`Turmite`] can walk on, defining the available headings.
+pub trait TurmiteCell: Cell {
+    /// Amount of headings, ordered clockwise
+    const HEADINGS: u8;
+
+    /// Coordinates of the cell next to `coords` in the `heading` direction
+    #[must_use]
+    fn forward(coords: &Self::Coordinates, heading: u8) -> Self::Coordinates;
+}
+
+/// Square grids use the 4 orthogonal headings: left, top, right and bottom
+#[cfg(feature = "2D")]
+impl TurmiteCell for MooreCell2d {
+    const HEADINGS: u8 = 4;
+
+    #[inline]
+    fn forward(coords: &Self::Coordinates, heading: u8) -> Self::Coordinates {
+        NeumannCell2d::forward(coords, heading)
+    }
+}
+
+/// The headings follow [`NeumannCell2d::NEIGHBOR_OFFSETS`]: left, top, right
+/// and bottom
+#[cfg(feature = "2D")]
+impl TurmiteCell for NeumannCell2d {
+    const HEADINGS: u8 = 4;
+
+    #[inline]
+    fn forward(coords: &Self::Coordinates, heading: u8) -> Self::Coordinates {
+        *coords + Self::NEIGHBOR_OFFSETS[usize::from(heading % Self::HEADINGS)]
+    }
+}
+
+/// The headings follow [`HexagonCell2d::NEIGHBOR_OFFSETS`]
+#[cfg(feature = "2D")]
+impl TurmiteCell for HexagonCell2d {
+    const HEADINGS: u8 = 6;
+
+    #[inline]
+    fn forward(coords: &Self::Coordinates, heading: u8) -> Self::Coordinates {
+        *coords + Self::NEIGHBOR_OFFSETS[usize::from(heading % Self::HEADINGS)]
+    }
+}
+
+/// [Turmite] agent walking on `C` cells, like Langton's ant.
+///
+/// Every simulation tick the turmite reads the color of its cell, then
+/// following its [`TurmiteRule`] paints the cell, turns, updates its internal
+/// state and moves forward by one cell. The plugin requires a
+/// [`TurmitePlugin`](crate::TurmitePlugin).
+///
+/// [Turmite]: https://en.wikipedia.org/wiki/Turmite
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct Turmite<C: TurmiteCell> {
+    /// The coordinates of the cell the turmite stands on
+    pub coords: C::Coordinates,
+    /// The turmite heading, between `0` and [`TurmiteCell::HEADINGS`]
+    pub heading: u8,
+    /// The internal state of the turmite
+    pub state: u8,
+    /// The turmite transition table
+    pub rule: TurmiteRule,
+    phantom: PhantomData<C>,
+}
+
+impl<C: TurmiteCell> Turmite<C> {
+    /// Instantiates a new turmite at `coords` following the `rule`, with the
+    /// first heading and internal state
+    #[must_use]
+    #[inline]
+    pub const fn new(coords: C::Coordinates, rule: TurmiteRule) -> Self {
+        Self {
+            coords,
+            heading: 0,
+            state: 0,
+            rule,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the initial `heading`
+    #[must_use]
+    #[inline]
+    pub const fn with_heading(mut self, heading: u8) -> Self {
+        self.heading = heading % C::HEADINGS;
+        self
+    }
+
+    /// Moves the turmite by one step from a cell of the given `color`.
+    ///
+    /// # Returns
+    ///
+    /// The color to paint the current cell with, before the move
+    pub fn step(&mut self, color: u8) -> u8 {
+        let transition = self.rule.transition(self.state, color);
+        self.heading = transition.turn.apply(self.heading, C::HEADINGS);
+        self.state = transition.state;
+        self.coords = C::forward(&self.coords, self.heading);
+        transition.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ant() {
+        let rule: TurmiteRule = "RRLLLRLLLRRR".parse().unwrap();
+        assert_eq!(rule.colors(), 12);
+        assert_eq!(rule.states(), 1);
+        assert_eq!(
+            rule.transition(0, 4),
+            TurmiteTransition::new(5, Turn::Left, 0)
+        );
+        assert_eq!(
+            rule.transition(0, 11),
+            TurmiteTransition::new(0, Turn::Right, 0)
+        );
+        let rule: TurmiteRule = "L2NNL1L2L1".parse().unwrap();
+        assert_eq!(rule.colors(), 6);
+        assert_eq!(rule.transition(0, 0).turn, Turn::Left2);
+        assert_eq!(rule.transition(0, 3).turn, Turn::Left);
+        assert_eq!(TurmiteRule::parse("rl"), Ok(TurmiteRule::langtons_ant()));
+        assert_eq!(
+            TurmiteRule::parse("RXL"),
+            Err(RuleParseError::InvalidCharacter('X'))
+        );
+        assert_eq!(TurmiteRule::parse("R3"), Err(RuleParseError::OutOfRange));
+        assert_eq!(TurmiteRule::parse(""), Err(RuleParseError::InvalidFormat));
+    }
+
+    #[test]
+    fn transition_table() {
+        let transition = TurmiteTransition::new(1, Turn::Left, 1);
+        assert!(TurmiteRule::new(vec![vec![transition; 2]; 2]).is_ok());
+        assert_eq!(
+            TurmiteRule::new(vec![vec![transition; 2]]),
+            Err(RuleParseError::OutOfRange)
+        );
+        assert_eq!(
+            TurmiteRule::new(vec![vec![transition; 2], vec![transition]]),
+            Err(RuleParseError::InvalidFormat)
+        );
+        assert_eq!(TurmiteRule::new(vec![]), Err(RuleParseError::InvalidFormat));
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Turn::Right.apply(3, 4), 0);
+        assert_eq!(Turn::Left.apply(0, 4), 3);
+        assert_eq!(Turn::UTurn.apply(1, 4), 3);
+        assert_eq!(Turn::UTurn.apply(1, 6), 4);
+        assert_eq!(Turn::Left2.apply(1, 6), 5);
+        assert_eq!(Turn::Right2.apply(5, 6), 1);
+        assert_eq!(Turn::None.apply(2, 6), 2);
+    }
+
+    #[cfg(feature = "2D")]
+    #[test]
+    fn langtons_ant_steps() {
+        use bevy::math::IVec2;
+
+        let mut ant = Turmite::<NeumannCell2d>::new(IVec2::ZERO, TurmiteRule::langtons_ant());
+        // Facing left, turns right on a white cell
+        assert_eq!(ant.step(0), 1);
+        assert_eq!((ant.coords, ant.heading), (IVec2::Y, 1));
+        // Turns left on a black cell
+        assert_eq!(ant.step(1), 0);
+        assert_eq!((ant.coords, ant.heading), (IVec2::new(-1, 1), 0));
+    }
+}
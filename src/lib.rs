@@ -29,6 +29,21 @@
 //! * `WireWorld3dPlugin`
 //! * `CyclicColors2dPlugin`
 //! * `CyclicColors3dPlugin`
+//! * `HighLife2dPlugin`
+//! * `DayAndNight2dPlugin`
+//! * `Seeds2dPlugin`
+//! * `BriansBrain2dPlugin`
+//! * `StarWars2dPlugin`
+//! * `Bugs2dPlugin`
+//! * `Majority2dPlugin`
+//! * `ForestFire2dPlugin`
+//! * `ProbabilisticLife2dPlugin`
+//! * `Lenia2dPlugin`
+//! * `SmoothLife2dPlugin`
+//! * `Critters2dPlugin`
+//! * `Tron2dPlugin`
+//! * `Sandpile2dPlugin`
+//! * `Elementary1dPlugin`
 //!
 //! Then you may use bevy as usual and add `impl Cell` and `impl CellState`
 //! components to the entities. The lib provides some implementations like
@@ -46,21 +61,272 @@
 //!   custom rules.
 //! * the [wireworld](https://github.com/ManevilleF/wireworld-rs) repository
 //!
+//! ### Life-like rules
+//!
+//! Any Life-like (totalistic) rule can be simulated with a
+//! `LifeLikeCellState`, using a `LifeLikeRule` parsed from the usual **B/S**
+//! (`B36/S23`) or **S/B** (`23/36`) notations:
+//!
+//! ```rust
+//! # #[cfg(feature = "2D")]
+//! use bevy_life::{CellularAutomatonPlugin, LifeLikeCellState, LifeLikeRule, MooreCell2d};
+//!
+//! # #[cfg(feature = "2D")]
+//! type MazeCellState = LifeLikeCellState<{ LifeLikeRule::bits("B3/S12345") }>;
+//! # #[cfg(feature = "2D")]
+//! type MazePlugin = CellularAutomatonPlugin<MooreCell2d, MazeCellState>;
+//! ```
+//!
+//! ### Generations rules
+//!
+//! "Generations" automata, where dying cells go through several refractory
+//! states, can be simulated with a `GenerationsCellState` using a
+//! `GenerationsRule` parsed from the **S/B/C** (`345/2/4`) or **B/S/C**
+//! (`B2/S345/C4`) notations.
+//!
+//! ### Larger-than-Life
+//!
+//! Ranged cells like `MooreRangeCell2d<R>`, `NeumannRangeCell2d<R>`,
+//! `CircularRangeCell2d<R>` or `CrossRangeCell2d<R>` have every cell within the
+//! `R` range as neighbors. Combined with a `LargerThanLifeCellState` using a
+//! `LargerThanLifeRule` parsed from the `R5,C0,M1,S34..58,B34..45,NM` notation,
+//! they allow to simulate Larger-than-Life automata. The neighborhood range and
+//! shape are defined by the cell type, building the plugin panics if they don't
+//! match the rule `R` and `N` sections.
+//!
+//! ### Weighted neighborhoods
+//!
+//! Cells may weight their neighbors through `Cell::neighbor_weights`, computed
+//! once per simulation, like the `GaussianRangeCell2d<R>` and
+//! `RingRangeCell2d<R>` ranged cells. Rules consuming weighted sums set
+//! `CellState::WEIGHTED` to `true` and implement
+//! `CellState::new_cell_state_weighted`, which receives the neighbor states
+//! with their weight.
+//!
+//! ### Continuous automata
+//!
+//! Continuous states, with values between `0.0` and `1.0`, allow to simulate
+//! automata like [Lenia](https://en.wikipedia.org/wiki/Lenia) with a
+//! `LeniaCellState`, following a `LeniaRule` defining its kernel, growth
+//! function and time step, or `SmoothLife` with a `SmoothLifeCellState`. They
+//! use large ranged cells weighted by the rule kernel, like
+//! `LeniaRangeCell2d<OrbiumRule, 13>` for the Orbium Lenia rule, and are
+//! colored in grayscale or as a heatmap (`ContinuousColoring`).
+//!
+//! ### Isotropic non-totalistic rules
+//!
+//! Rules depending on the arrangement of the neighbors, like `B2-a/S12`, can be
+//! simulated on `MooreCell2d` cells with an `IsotropicCellState` using an
+//! `IsotropicRule` parsed from the Hensel notation.
+//!
+//! ### Positional rules
+//!
+//! Rules depending on the neighbor positions, like isotropic or directional
+//! rules (wind, flow, etc), implement `CellState::new_cell_state_positional`
+//! which receives the neighbor states with their index in the order defined by
+//! the cell, including missing neighbors. The provided cells expose their
+//! `NEIGHBOR_OFFSETS` (`neighbor_offsets()` for ranged cells) to map these
+//! indices to directions.
+//!
+//! ### Context rules
+//!
+//! Rules depending on the cell position, the generation or the elapsed time,
+//! like gradients, obstacles or rules alternating every generation, implement
+//! `CellState::new_cell_state_with_context`. The given `CellContext` provides
+//! the cell, its coordinates, the current generation and the simulated
+//! generation duration. Cells reading the generation, the duration or the
+//! random stream are evaluated again in the next generation, even if their
+//! neighborhood is stable.
+//!
+//! ### Randomness
+//!
+//! Stochastic rules draw random values from `CellContext::rng`, a random stream
+//! derived from the `SimulationRng` resource seed, the cell coordinates and the
+//! generation. Simulations are reproducible for a given seed, with or without
+//! `SimulationBatch`. The provided `ForestFireCellState` and
+//! `ProbabilisticLifeCellState` are stochastic.
+//!
+//! ### Block automata
+//!
+//! Block automata like Critters or Tron update 2x2 blocks of cells at once,
+//! with a partition shifted every generation (Margolus neighborhood). Implement
+//! `BlockRule` and use the `BlockCellularAutomatonPlugin` on a `MooreCell2d`
+//! grid:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! # #[cfg(feature = "2D")]
+//! use bevy_life::{BlockCellularAutomatonPlugin, CrittersRule};
+//!
+//! # #[cfg(feature = "2D")]
+//! fn main() {
+//!     App::new()
+//!         .add_plugins(BlockCellularAutomatonPlugin::<CrittersRule>::default())
+//!         // ...
+//!         .run();
+//! }
+//! # #[cfg(not(feature = "2D"))]
+//! # fn main() {}
+//! ```
+//!
+//! ### One-dimensional automata
+//!
+//! Elementary cellular automata run on `Cell1d` cells with an
+//! `ElementaryCellState` following a Wolfram rule number, like
+//! `Rule30CellState` or `Rule110CellState`. Totalistic automata with `K`
+//! colors and any radius use a `TotalisticCellState` with a Wolfram totalistic
+//! code. The `History1dPlugin` lays out every generation as a new row to
+//! visualize the classic space-time diagrams:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! # #[cfg(feature = "1D")]
+//! use bevy_life::{Elementary1dPlugin, History1dPlugin, Rule110CellState};
+//!
+//! # #[cfg(feature = "1D")]
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((
+//!             Elementary1dPlugin::<110>::default(),
+//!             History1dPlugin::<Rule110CellState>::default().with_cell_size(4.0),
+//!         ))
+//!         // ...
+//!         .run();
+//! }
+//! # #[cfg(not(feature = "1D"))]
+//! # fn main() {}
+//! ```
+//!
+//! ### Turmites
+//!
+//! [Turmites](https://en.wikipedia.org/wiki/Turmite), like Langton's ant, are
+//! agents walking on `MooreCell2d`, `NeumannCell2d` or `HexagonCell2d` cells.
+//! A `Turmite` entity has a position, a heading, an internal state and a
+//! `TurmiteRule`, parsed from turn strings like `RL` or `RRLLLRLLLRRR` or built
+//! from a multi-state transition table. The `TurmitePlugin` moves the turmites
+//! one step per generation of the matching `CellularAutomatonPlugin`, painting
+//! cells with a `TurmiteState` like the `TurmiteCellState`:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! # #[cfg(feature = "2D")]
+//! use bevy_life::{CellularAutomatonPlugin, NeumannCell2d, TurmiteCellState, TurmitePlugin};
+//!
+//! # #[cfg(feature = "2D")]
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((
+//!             CellularAutomatonPlugin::<NeumannCell2d, TurmiteCellState>::default(),
+//!             TurmitePlugin::<NeumannCell2d>::default(),
+//!         ))
+//!         // ...
+//!         .run();
+//! }
+//! # #[cfg(not(feature = "2D"))]
+//! # fn main() {}
+//! ```
+//!
+//! ### Sandpiles
+//!
+//! The [abelian sandpile](https://en.wikipedia.org/wiki/Abelian_sandpile_model)
+//! model runs on `NeumannCell2d`, `MooreCell2d` or `HexagonCell2d` cells with
+//! a `SandpilePlugin`, cells holding as many `SandpileCellState` grains as
+//! they have neighbors topple. Grains are dropped with `DropGrains` messages
+//! and every avalanche sends an `Avalanche` message with its size, area and
+//! duration. The `SandpileRelaxation` resource relaxes the pile to stability
+//! every frame:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! # #[cfg(feature = "2D")]
+//! use bevy_life::Sandpile2dPlugin;
+//!
+//! # #[cfg(feature = "2D")]
+//! fn main() {
+//!     App::new()
+//!         .add_plugins(Sandpile2dPlugin::default().with_relaxation())
+//!         // ...
+//!         .run();
+//! }
+//! # #[cfg(not(feature = "2D"))]
+//! # fn main() {}
+//! ```
+//!
 //! ### Pausing
 //!
 //! Inserting a `SimulationPause` resource will pause the simulation, removing
 //! it wil resume the it.
 //!
+//! While paused, inserting a `SimulationStep` resource will advance the
+//! simulation by the given amount of generations.
+//!
+//! ### Fast-forward
+//!
+//! Inserting a `SimulationTickBudget` resource will run multiple generations
+//! every simulation tick, either a fixed amount or as many as fit in a time
+//! budget.
+//!
+//! ### Scheduling
+//!
+//! By default the cells are updated in the `Update` schedule, use
+//! `CellularAutomatonPlugin::with_schedule` to run the simulation in another
+//! schedule, like `FixedUpdate` for a deterministic simulation rate.
+//!
+//! ### Speed
+//!
+//! The `SimulationSpeed<C, S>` resource, initialized from
+//! `CellularAutomatonPlugin::with_time_step`, defines the simulation tick rate
+//! and can be edited at runtime.
+//!
 //! ### Parallel execution and batching
 //!
 //! Inserting a `SimulationBatch` resource will allow parallel computation of
 //! cells with custom batch sizes.
 //!
+//! ### Toroidal worlds
+//!
+//! Wrapping a cell in a `ToroidalCell` makes its neighbor coordinates wrap
+//! around a world of fixed dimensions, allowing patterns to leave an edge and
+//! reappear on the opposite one. Every provided cell type implements the
+//! required `WrappingCell` trait.
+//!
+//! ### Boundaries
+//!
+//! Neighbors without a matching cell are ignored by default, use
+//! `CellularAutomatonPlugin::with_boundary` to give them a fixed state (like a
+//! *dead* state), to copy the evaluated cell state or to mirror the world
+//! through its edges. The `BoundaryCondition<C, S>` resource holds the `Boundary`
+//! of every simulation and can be edited at runtime.
+//!
+//! ### Unbounded worlds
+//!
+//! Inserting an `UnboundedWorld<C, S>` resource lets the simulation grow beyond
+//! the spawned cells: cells are spawned, through a customizable hook, where
+//! births happen next to existing cells and despawned when they return to the
+//! *dead* state.
+//!
+//! ### Active cells
+//!
+//! Only the cells whose own state or one of their neighbor states changed
+//! during the previous generation are evaluated, so stable areas are never
+//! recomputed. Rules which may change a cell state spontaneously must opt out
+//! by setting `CellState::NEIGHBOR_DRIVEN` to `false`.
+//!
+//! ### Generations
+//!
+//! Every plugin adds a `SimulationGeneration<C, S>` resource, incremented every
+//! simulation tick, and sends a `GenerationAdvanced<C, S>` message with the new
+//! generation and the amount of changed cells.
+//!
 //! ## Cargo Features
 //!
 //! No feature is required for the plugin to work and the main traits `Cell` and
 //! `CellState` are always available. But you may enable the following features
 //!
+//! * `1D` (enabled by default): Enables 1D types like:
+//!   * `Cell1d` (line cell with neighbors within a radius)
+//!   * `ElementaryCellState` and `TotalisticCellState`
+//!   * `History1dPlugin` and the `Elementary1dPlugin` preset
 //! * `2D` (enabled by default): Enables 2D types like:
 //!   * `MooreCell2d` (square cell with 8 neighbors)
 //!   * `NeumannCell2d` (square cell with 4 neighbors)
@@ -94,19 +360,42 @@
 )]
 #![allow(clippy::default_trait_access, clippy::module_name_repetitions)]
 
-use bevy::{log, prelude::*, time::common_conditions::on_timer};
+use bevy::{
+    ecs::{
+        schedule::{InternedScheduleLabel, IntoScheduleConfigs, ScheduleLabel},
+        system::ScheduleSystem,
+    },
+    log,
+    prelude::*,
+};
 use std::{marker::PhantomData, time::Duration};
 
 mod components;
+mod messages;
 mod resources;
 mod systems;
 
-use systems::cells::{handle_cells, handle_new_cells};
+#[cfg(feature = "2D")]
+use systems::blocks::handle_blocks;
+use systems::cells::{
+    handle_cells, handle_new_cells, handle_new_states, handle_removed_cells, handle_removed_states,
+    simulation_tick,
+};
+#[cfg(feature = "1D")]
+use systems::history::record_history;
+use systems::sandpile::{drop_grains, handle_sandpile};
+use systems::turmites::handle_turmites;
 
-use crate::systems::cells::handle_removed_cells;
 pub use components::*;
+pub use messages::*;
 pub use resources::*;
 
+#[cfg(feature = "1D")]
+/// Cellular automaton plugin type for elementary cellular automata following
+/// the Wolfram rule `RULE`, like Rule 30 or Rule 110
+pub type Elementary1dPlugin<const RULE: u8> =
+    CellularAutomatonPlugin<components::Cell1d, ElementaryCellState<RULE>>;
+
 #[cfg(feature = "2D")]
 /// Cellular automaton plugin type for Conway's Game of life in 2D.
 pub type GameOfLife2dPlugin = CellularAutomatonPlugin<components::MooreCell2d, ConwayCellState>;
@@ -157,6 +446,81 @@ pub type CyclicColors2dPlugin<const N: usize> =
 pub type CyclicColors3dPlugin<const N: usize> =
     CellularAutomatonPlugin<components::MooreCell3d, CyclicColorCellState<N>>;
 
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the `HighLife` (`B36/S23`) Game of life
+/// variation in 2D
+pub type HighLife2dPlugin = CellularAutomatonPlugin<components::MooreCell2d, HighLifeCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the Day & Night (`B3678/S34678`) Game of
+/// life variation in 2D
+pub type DayAndNight2dPlugin =
+    CellularAutomatonPlugin<components::MooreCell2d, DayAndNightCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the Seeds (`B2/S`) Game of life variation
+/// in 2D
+pub type Seeds2dPlugin = CellularAutomatonPlugin<components::MooreCell2d, SeedsCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the Brian's Brain (`/2/3`) generations
+/// automaton in 2D
+pub type BriansBrain2dPlugin =
+    CellularAutomatonPlugin<components::MooreCell2d, BriansBrainCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the Star Wars (`345/2/4`) generations
+/// automaton in 2D
+pub type StarWars2dPlugin = CellularAutomatonPlugin<components::MooreCell2d, StarWarsCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the Bugs (`R5,C0,M1,S34..58,B34..45,NM`)
+/// Larger-than-Life automaton in 2D
+pub type Bugs2dPlugin = CellularAutomatonPlugin<components::MooreRangeCell2d<5>, BugsCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the Majority
+/// (`R4,C0,M1,S41..81,B41..81,NM`) Larger-than-Life automaton in 2D
+pub type Majority2dPlugin =
+    CellularAutomatonPlugin<components::MooreRangeCell2d<4>, MajorityCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the stochastic forest-fire model in 2D
+pub type ForestFire2dPlugin =
+    CellularAutomatonPlugin<components::MooreCell2d, DefaultForestFireCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for a probabilistic Game of life variation,
+/// where transitions happen half of the time, in 2D
+pub type ProbabilisticLife2dPlugin =
+    CellularAutomatonPlugin<components::MooreCell2d, ProbabilisticConwayCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the Orbium Lenia continuous automaton
+/// in 2D
+pub type Lenia2dPlugin =
+    CellularAutomatonPlugin<components::LeniaRangeCell2d<OrbiumRule, 13>, OrbiumCellState>;
+
+#[cfg(feature = "2D")]
+/// Cellular automaton plugin type for the classic `SmoothLife` continuous
+/// automaton in 2D
+pub type SmoothLife2dPlugin = CellularAutomatonPlugin<
+    components::SmoothLifeRangeCell2d<DefaultSmoothLifeRule, 21>,
+    DefaultSmoothLifeCellState,
+>;
+
+#[cfg(feature = "2D")]
+/// Block cellular automaton plugin type for the Critters reversible rule
+pub type Critters2dPlugin = BlockCellularAutomatonPlugin<CrittersRule>;
+
+#[cfg(feature = "2D")]
+/// Block cellular automaton plugin type for the Tron rule
+pub type Tron2dPlugin = BlockCellularAutomatonPlugin<TronRule>;
+
+#[cfg(feature = "2D")]
+/// Sandpile plugin type for the classic abelian sandpile on a 4 neighbor grid
+pub type Sandpile2dPlugin = SandpilePlugin<components::NeumannCell2d>;
+
 /// System set variant for each cellular automaton step
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
 pub enum LifeSystemSet {
@@ -164,12 +528,89 @@ pub enum LifeSystemSet {
     /// [`CellularAutomatonPlugin::use_cell_map`])
     NewCells,
     /// Despawned Cell removal from [`CellMap`] (Requires
-    /// [`CellularAutomatonPlugin::use_cell_map`])
+    /// [`CellularAutomatonPlugin::use_cell_map`]) and [`CellStateMap`]
     RemovedCells,
+    /// Spawned and changed cell states update in [`CellStateMap`]
+    StateMapUpdate,
     /// Cell life tick update system set
     CellUpdate,
 }
 
+/// Schedule of the cell update systems of the simulation of `C` cells with `S`
+/// states, for the plugins extending it
+#[derive(Resource)]
+struct SimulationSchedule<C, S>(InternedScheduleLabel, PhantomData<(C, S)>);
+
+/// Sets up the resources, messages and systems shared by every simulation
+/// plugin of `C` cells with `S` states, `update` being the system computing
+/// the generations.
+///
+/// # Returns
+///
+/// The schedule of the cell update systems
+///
+/// # Panics
+///
+/// Panics if another plugin already simulates `C` cells with `S` states, as
+/// both plugins would corrupt their shared [`CellStateMap`], or if the `C`
+/// cells don't match the `S` rules (see [`CellState::check_cell_type`]).
+fn build_simulation<C, S, M>(
+    app: &mut App,
+    plugin_name: &str,
+    tick_time_step: Option<f64>,
+    schedule: Option<InternedScheduleLabel>,
+    update: impl IntoScheduleConfigs<ScheduleSystem, M>,
+) -> InternedScheduleLabel
+where
+    C: Cell,
+    S: CellState,
+{
+    assert!(
+        !app.world().contains_resource::<CellStateMap<C, S>>(),
+        "{plugin_name} conflicts with another plugin simulating the same `{}` cells with `{}` states",
+        std::any::type_name::<C>(),
+        std::any::type_name::<S>(),
+    );
+    S::check_cell_type::<C>();
+    let schedule = schedule.unwrap_or_else(|| Update.intern());
+    app.insert_resource(CellStateMap::<C, S>::default())
+        .insert_resource(SimulationGeneration::<C, S>::default())
+        .insert_resource(SimulationSchedule::<C, S>(schedule, PhantomData))
+        .add_message::<GenerationAdvanced<C, S>>();
+    let speed = tick_time_step.map_or_else(SimulationSpeed::every_frame, |step| {
+        SimulationSpeed::from_interval(Duration::from_secs_f64(step))
+    });
+    app.insert_resource::<SimulationSpeed<C, S>>(speed)
+        .add_systems(
+            PostUpdate,
+            handle_removed_states::<C, S>.in_set(LifeSystemSet::RemovedCells),
+        )
+        .add_systems(
+            schedule,
+            (
+                handle_new_states::<C, S>
+                    .in_set(LifeSystemSet::StateMapUpdate)
+                    .before(LifeSystemSet::CellUpdate),
+                update
+                    .run_if(simulation_tick::<C, S>)
+                    .in_set(LifeSystemSet::CellUpdate),
+            ),
+        );
+
+    #[cfg(feature = "auto-coloring")]
+    {
+        #[cfg(feature = "2D")]
+        {
+            app.add_systems(Update, systems::coloring::color_sprites::<S>);
+        }
+        #[cfg(feature = "3D")]
+        {
+            log::warn!("No auto coloring is available for 3D materials");
+        }
+    }
+    schedule
+}
+
 /// Generic Cellular Automaton plugin. It will register systems for the matching
 /// `Cell` and `CellState` types.
 ///
@@ -179,9 +620,20 @@ pub enum LifeSystemSet {
 pub struct CellularAutomatonPlugin<C, S> {
     /// Custom time step (in seconds) constraint value for the systems. If not
     /// set, the systems will run every frame.
+    ///
+    /// This is the initial value of the [`SimulationSpeed`] resource, which can
+    /// be edited at runtime.
     pub tick_time_step: Option<f64>,
     /// Should a [`CellMap`] be resource be added and filled ?
     pub use_cell_map: bool,
+    /// Custom schedule for the cell update systems, if not set the systems will
+    /// run in [`Update`].
+    ///
+    /// Cell removals and [`CellMap`] updates always run in [`PostUpdate`]
+    pub schedule: Option<InternedScheduleLabel>,
+    /// How missing neighbors are handled, initial value of the
+    /// [`BoundaryCondition`] resource
+    pub boundary: Boundary<S>,
     /// Phantom data for the `C` (`Cell`) type
     pub phantom_c: PhantomData<C>,
     /// Phantom data for the `S` (`CellState`) type
@@ -192,6 +644,14 @@ impl<C: Cell, S: CellState> Plugin for CellularAutomatonPlugin<C, S> {
     fn build(&self, app: &mut App) {
         // app.register_type::<C>().register_type::<S>().
         // register_type::<CellMap::<C>>();
+        build_simulation::<C, S, _>(
+            app,
+            self.name(),
+            self.tick_time_step,
+            self.schedule,
+            handle_cells::<C, S>,
+        );
+        app.insert_resource(BoundaryCondition::<C, S>::new(self.boundary.clone()));
         if self.use_cell_map {
             app.insert_resource(CellMap::<C>::default());
             app.add_systems(
@@ -202,32 +662,6 @@ impl<C: Cell, S: CellState> Plugin for CellularAutomatonPlugin<C, S> {
                 ),
             );
         }
-        if let Some(time_step) = self.tick_time_step {
-            let duration = Duration::from_secs_f64(time_step);
-            app.add_systems(
-                Update,
-                handle_cells::<C, S>
-                    .run_if(on_timer(duration))
-                    .in_set(LifeSystemSet::CellUpdate),
-            );
-        } else {
-            app.add_systems(
-                Update,
-                handle_cells::<C, S>.in_set(LifeSystemSet::CellUpdate),
-            );
-        }
-
-        #[cfg(feature = "auto-coloring")]
-        {
-            #[cfg(feature = "2D")]
-            {
-                app.add_systems(Update, systems::coloring::color_sprites::<S>);
-            }
-            #[cfg(feature = "3D")]
-            {
-                log::warn!("No auto coloring is available for 3D materials");
-            }
-        }
         log::info!("Loaded cellular automaton plugin");
     }
 }
@@ -240,6 +674,8 @@ impl<C, S> CellularAutomatonPlugin<C, S> {
         Self {
             tick_time_step: None,
             use_cell_map: false,
+            schedule: None,
+            boundary: Boundary::Ignore,
             phantom_c: PhantomData,
             phantom_s: PhantomData,
         }
@@ -253,6 +689,24 @@ impl<C, S> CellularAutomatonPlugin<C, S> {
         self
     }
 
+    /// Sets a custom `schedule` for the cell update systems, like
+    /// [`FixedUpdate`] for a deterministic simulation rate
+    #[must_use]
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(schedule.intern());
+        self
+    }
+
+    /// Sets the `boundary` condition for missing neighbors
+    #[must_use]
+    #[inline]
+    pub fn with_boundary(mut self, boundary: Boundary<S>) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
     /// The plugin will set a [`CellMap`] resource and dynamically update it
     #[must_use]
     #[inline]
@@ -267,3 +721,424 @@ impl<C, S> Default for CellularAutomatonPlugin<C, S> {
         Self::new()
     }
 }
+
+#[cfg(feature = "2D")]
+/// Block cellular automaton plugin, updating a [`MooreCell2d`] grid by 2x2
+/// Margolus blocks following the `R` [`BlockRule`].
+///
+/// The block partition is shifted by one cell on both axes every generation.
+/// Only the blocks with changed cells are evaluated, unless the rule disables
+/// [`BlockRule::CHANGE_DRIVEN`], and the blocks are evaluated in parallel with
+/// a [`SimulationBatch`]. The plugin uses the same resources as a
+/// `CellularAutomatonPlugin<MooreCell2d, R::State>`, adding both plugins
+/// panics.
+pub struct BlockCellularAutomatonPlugin<R> {
+    /// Custom time step (in seconds) constraint value for the systems. If not
+    /// set, the systems will run every frame.
+    ///
+    /// This is the initial value of the [`SimulationSpeed`] resource, which can
+    /// be edited at runtime.
+    pub tick_time_step: Option<f64>,
+    /// Custom schedule for the cell update systems, if not set the systems will
+    /// run in [`Update`].
+    ///
+    /// Cell removals always run in [`PostUpdate`]
+    pub schedule: Option<InternedScheduleLabel>,
+    /// Phantom data for the `R` (`BlockRule`) type
+    pub phantom: PhantomData<R>,
+}
+
+#[cfg(feature = "2D")]
+impl<R: BlockRule> Plugin for BlockCellularAutomatonPlugin<R> {
+    fn build(&self, app: &mut App) {
+        build_simulation::<MooreCell2d, R::State, _>(
+            app,
+            self.name(),
+            self.tick_time_step,
+            self.schedule,
+            handle_blocks::<R>,
+        );
+        log::info!("Loaded block cellular automaton plugin");
+    }
+}
+
+#[cfg(feature = "2D")]
+impl<R> BlockCellularAutomatonPlugin<R> {
+    /// Instantiates Self with default values
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            tick_time_step: None,
+            schedule: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets a custom `tick_time_step` value for systems execution
+    #[must_use]
+    #[inline]
+    pub const fn with_time_step(mut self, tick_time_step: f64) -> Self {
+        self.tick_time_step = Some(tick_time_step);
+        self
+    }
+
+    /// Sets a custom `schedule` for the cell update systems, like
+    /// [`FixedUpdate`] for a deterministic simulation rate
+    #[must_use]
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(schedule.intern());
+        self
+    }
+}
+
+#[cfg(feature = "2D")]
+impl<R> Default for BlockCellularAutomatonPlugin<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "1D")]
+/// Space-time diagram plugin for 1D automata.
+///
+/// Every generation of the `Cell1d<R>` cells with `S` states is laid out as a
+/// new row of [`HistoryCell`] entities, below the previous one.
+///
+/// Every row entity has the recorded `S` state and a [`Transform`] matching
+/// its position in the diagram, and a sprite with the `auto-coloring`
+/// feature. The plugin requires a `CellularAutomatonPlugin<Cell1d<R>, S>`.
+///
+/// A single row is recorded every simulation tick, with a
+/// [`SimulationTickBudget`] the intermediate generations are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_life::{Elementary1dPlugin, History1dPlugin, Rule30CellState};
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             Elementary1dPlugin::<30>::default(),
+///             History1dPlugin::<Rule30CellState>::default().with_cell_size(4.0),
+///         ))
+///         // ...
+///         .run();
+/// }
+/// ```
+pub struct History1dPlugin<S, const R: i32 = 1> {
+    /// Size of a history cell, initial value of the [`SpaceTimeHistory`]
+    /// resource
+    pub cell_size: f32,
+    /// Maximum amount of recorded rows, initial value of the
+    /// [`SpaceTimeHistory`] resource
+    pub max_rows: Option<u64>,
+    /// Schedule of the cellular automaton plugin, if not set the history is
+    /// recorded in [`Update`].
+    pub schedule: Option<InternedScheduleLabel>,
+    /// Phantom data for the `S` (`CellState`) type
+    pub phantom: PhantomData<S>,
+}
+
+#[cfg(feature = "1D")]
+impl<S: CellState, const R: i32> Plugin for History1dPlugin<S, R> {
+    fn build(&self, app: &mut App) {
+        let mut history = SpaceTimeHistory::<S, R>::new(self.cell_size);
+        history.max_rows = self.max_rows;
+        let schedule = self.schedule.unwrap_or_else(|| Update.intern());
+        app.insert_resource(history).add_systems(
+            schedule,
+            record_history::<S, R>
+                .after(LifeSystemSet::StateMapUpdate)
+                .before(LifeSystemSet::CellUpdate),
+        );
+        log::info!("Loaded 1D history plugin");
+    }
+}
+
+#[cfg(feature = "1D")]
+impl<S, const R: i32> History1dPlugin<S, R> {
+    /// Instantiates Self with default values
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            cell_size: 1.0,
+            max_rows: None,
+            schedule: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets a custom `cell_size` for the history cells
+    #[must_use]
+    #[inline]
+    pub const fn with_cell_size(mut self, cell_size: f32) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Sets the maximum amount of recorded rows
+    #[must_use]
+    #[inline]
+    pub const fn with_max_rows(mut self, max_rows: u64) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Sets the `schedule` of the matching cellular automaton plugin, see
+    /// [`CellularAutomatonPlugin::with_schedule`]
+    #[must_use]
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(schedule.intern());
+        self
+    }
+}
+
+#[cfg(feature = "1D")]
+impl<S, const R: i32> Default for History1dPlugin<S, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turmite plugin, moving the [`Turmite`] agents walking on `C` cells with `S`
+/// states, like Langton's ant.
+///
+/// For every generation of the simulation tick, right after the cell update,
+/// each turmite reads the color of its cell, paints it, turns and moves forward
+/// following its [`TurmiteRule`]. The turmites move one after another, and read
+/// and write the cell states through the [`CellStateMap`], which unlike the
+/// [`CellMap`] is up to date during the [`LifeSystemSet::CellUpdate`] set.
+/// Cells without a matching entity are read as the `0` color and can't be
+/// painted.
+///
+/// The plugin requires a `CellularAutomatonPlugin<C, S>`: the turmites move in
+/// its schedule, one step per [`GenerationAdvanced`] generation, so they follow
+/// its [`SimulationSpeed`], pause, step and budget resources. A grid of
+/// [`TurmiteCellState`] cells is stable, its simulation advances a single
+/// generation every tick regardless of the [`SimulationTickBudget`].
+///
+/// # Panics
+///
+/// Panics if the `CellularAutomatonPlugin<C, S>` isn't added before this
+/// plugin.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2D")]
+/// use bevy_life::{
+///     CellularAutomatonPlugin, NeumannCell2d, Turmite, TurmiteCellState, TurmitePlugin,
+/// };
+///
+/// # #[cfg(feature = "2D")]
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             CellularAutomatonPlugin::<NeumannCell2d, TurmiteCellState>::default(),
+///             TurmitePlugin::<NeumannCell2d>::default(),
+///         ))
+///         .add_systems(Startup, spawn_ant)
+///         // ...
+///         .run();
+/// }
+///
+/// # #[cfg(feature = "2D")]
+/// fn spawn_ant(mut commands: Commands) {
+///     // Spawn the cells ...
+///     let rule = "RRLLLRLLLRRR".parse().unwrap();
+///     commands.spawn(Turmite::<NeumannCell2d>::new(IVec2::ZERO, rule));
+/// }
+/// # #[cfg(not(feature = "2D"))]
+/// # fn main() {}
+/// ```
+pub struct TurmitePlugin<C, S = TurmiteCellState> {
+    /// Phantom data for the `C` (`Cell`) type
+    pub phantom_c: PhantomData<C>,
+    /// Phantom data for the `S` (`CellState`) type
+    pub phantom_s: PhantomData<S>,
+}
+
+impl<C: TurmiteCell, S: TurmiteState> Plugin for TurmitePlugin<C, S> {
+    fn build(&self, app: &mut App) {
+        let Some(&SimulationSchedule(schedule, _)) =
+            app.world().get_resource::<SimulationSchedule<C, S>>()
+        else {
+            panic!(
+                "TurmitePlugin requires a CellularAutomatonPlugin simulating the same `{}` cells with `{}` states, added before it",
+                std::any::type_name::<C>(),
+                std::any::type_name::<S>(),
+            );
+        };
+        app.add_systems(
+            schedule,
+            handle_turmites::<C, S>
+                .after(handle_cells::<C, S>)
+                .in_set(LifeSystemSet::CellUpdate),
+        );
+        log::info!("Loaded turmite plugin");
+    }
+}
+
+impl<C, S> TurmitePlugin<C, S> {
+    /// Instantiates Self with default values
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            phantom_c: PhantomData,
+            phantom_s: PhantomData,
+        }
+    }
+}
+
+impl<C, S> Default for TurmitePlugin<C, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [Abelian sandpile] plugin, toppling the [`SandpileCellState`] grains of the
+/// `C` cells.
+///
+/// Grains are dropped on the pile through [`DropGrains`] messages. Every
+/// simulation tick a toppling generation is computed, every unstable cell
+/// giving one grain to each of its neighbors. Inserting a
+/// [`SandpileRelaxation`] resource (see [`Self::with_relaxation`]) relaxes the
+/// pile to stability every tick instead.
+///
+/// An [`Avalanche`] message is sent with the avalanche statistics every time
+/// the pile becomes stable after toppling.
+///
+/// The plugin uses the same resources as a
+/// `CellularAutomatonPlugin<C, SandpileCellState>`, adding both plugins
+/// panics.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2D")]
+/// use bevy_life::{Avalanche, DropGrains, NeumannCell2d, Sandpile2dPlugin};
+///
+/// # #[cfg(feature = "2D")]
+/// fn main() {
+///     App::new()
+///         .add_plugins(Sandpile2dPlugin::default().with_relaxation())
+///         .add_systems(Update, (drop_grain, log_avalanches))
+///         // ...
+///         .run();
+/// }
+///
+/// # #[cfg(feature = "2D")]
+/// fn drop_grain(mut drops: MessageWriter<DropGrains<NeumannCell2d>>) {
+///     drops.write(DropGrains::new(IVec2::ZERO, 1));
+/// }
+///
+/// # #[cfg(feature = "2D")]
+/// fn log_avalanches(mut avalanches: MessageReader<Avalanche<NeumannCell2d>>) {
+///     for avalanche in avalanches.read() {
+///         info!("Avalanche of size {}", avalanche.size);
+///     }
+/// }
+/// # #[cfg(not(feature = "2D"))]
+/// # fn main() {}
+/// ```
+///
+/// [Abelian sandpile]: https://en.wikipedia.org/wiki/Abelian_sandpile_model
+pub struct SandpilePlugin<C> {
+    /// Custom time step (in seconds) constraint value for the systems. If not
+    /// set, the systems will run every frame.
+    ///
+    /// This is the initial value of the [`SimulationSpeed`] resource, which can
+    /// be edited at runtime.
+    pub tick_time_step: Option<f64>,
+    /// Should the pile be relaxed to stability every tick, inserting a
+    /// [`SandpileRelaxation`] resource
+    pub relaxation: bool,
+    /// Custom schedule for the cell update systems, if not set the systems will
+    /// run in [`Update`].
+    ///
+    /// Cell removals always run in [`PostUpdate`]
+    pub schedule: Option<InternedScheduleLabel>,
+    /// Phantom data for the `C` (`Cell`) type
+    pub phantom: PhantomData<C>,
+}
+
+impl<C: Cell> Plugin for SandpilePlugin<C> {
+    fn build(&self, app: &mut App) {
+        let schedule = build_simulation::<C, SandpileCellState, _>(
+            app,
+            self.name(),
+            self.tick_time_step,
+            self.schedule,
+            handle_sandpile::<C>,
+        );
+        app.add_message::<DropGrains<C>>()
+            .add_message::<Avalanche<C>>()
+            .add_systems(
+                schedule,
+                drop_grains::<C>
+                    .after(LifeSystemSet::StateMapUpdate)
+                    .before(LifeSystemSet::CellUpdate),
+            );
+        if self.relaxation {
+            app.insert_resource(SandpileRelaxation::<C>::new());
+        }
+        log::info!("Loaded sandpile plugin");
+    }
+}
+
+impl<C> SandpilePlugin<C> {
+    /// Instantiates Self with default values
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            tick_time_step: None,
+            relaxation: false,
+            schedule: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets a custom `tick_time_step` value for systems execution
+    #[must_use]
+    #[inline]
+    pub const fn with_time_step(mut self, tick_time_step: f64) -> Self {
+        self.tick_time_step = Some(tick_time_step);
+        self
+    }
+
+    /// The pile will be relaxed to stability every tick
+    #[must_use]
+    #[inline]
+    pub const fn with_relaxation(mut self) -> Self {
+        self.relaxation = true;
+        self
+    }
+
+    /// Sets a custom `schedule` for the cell update systems, like
+    /// [`FixedUpdate`] for a deterministic simulation rate
+    #[must_use]
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(schedule.intern());
+        self
+    }
+}
+
+impl<C> Default for SandpilePlugin<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::components::Cell;
+use bevy::prelude::Message;
+use std::marker::PhantomData;
+
+/// Message sent every time the
+/// [`CellularAutomatonPlugin`](crate::CellularAutomatonPlugin) simulation
+/// matching the `C` (`Cell`) and `S` (`CellState`) types advances a
+/// generation.
+#[derive(Debug, Message)]
+pub struct GenerationAdvanced<C, S> {
+    /// The new generation, matching
+    /// [`SimulationGeneration`](crate::SimulationGeneration)
+    pub generation: u64,
+    /// The amount of cells whose state changed during the generation
+    pub changed_cells: usize,
+    phantom: PhantomData<(C, S)>,
+}
+
+impl<C, S> GenerationAdvanced<C, S> {
+    pub(crate) const fn new(generation: u64, changed_cells: usize) -> Self {
+        Self {
+            generation,
+            changed_cells,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<C, S> Clone for GenerationAdvanced<C, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, S> Copy for GenerationAdvanced<C, S> {}
+
+/// Message to send to drop `grains` on the cell at `coords` of the
+/// [`SandpilePlugin`](crate::SandpilePlugin) pile matching the `C` (`Cell`)
+/// type.
+///
+/// The grains are dropped every frame, even if the simulation is paused or
+/// doesn't tick, drops on missing cells are ignored.
+#[derive(Debug, Clone, Message)]
+pub struct DropGrains<C: Cell> {
+    /// The coordinates of the cell receiving the grains
+    pub coords: C::Coordinates,
+    /// The amount of dropped grains
+    pub grains: u32,
+}
+
+impl<C: Cell> DropGrains<C> {
+    /// Instantiates a new drop of `grains` on the cell at `coords`
+    #[must_use]
+    #[inline]
+    pub const fn new(coords: C::Coordinates, grains: u32) -> Self {
+        Self { coords, grains }
+    }
+}
+
+/// Message sent by the [`SandpilePlugin`](crate::SandpilePlugin) pile
+/// matching the `C` (`Cell`) type once an avalanche ends, a sequence of
+/// generations with toppling cells followed by a stable generation.
+#[derive(Debug, Message)]
+pub struct Avalanche<C> {
+    /// The avalanche size, the total amount of topplings
+    pub size: u64,
+    /// The amount of distinct cells which toppled
+    pub area: usize,
+    /// The amount of generations with toppling cells
+    pub duration: u32,
+    /// The amount of grains which fell off the edges of the pile
+    pub lost_grains: u64,
+    /// The generation where the pile became stable
+    pub generation: u64,
+    phantom: PhantomData<C>,
+}
+
+impl<C> Avalanche<C> {
+    pub(crate) const fn new(
+        size: u64,
+        area: usize,
+        duration: u32,
+        lost_grains: u64,
+        generation: u64,
+    ) -> Self {
+        Self {
+            size,
+            area,
+            duration,
+            lost_grains,
+            generation,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<C> Clone for Avalanche<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Avalanche<C> {}
@@ -0,0 +1,44 @@
+use bevy::prelude::Resource;
+use std::marker::PhantomData;
+
+/// Settings of the space-time diagram of a 1D automaton.
+///
+/// Every generation of `Cell1d<R>` cells with `S` states is laid out as a new
+/// row of [`HistoryCell`](crate::HistoryCell) entities, below the previous
+/// one.
+///
+/// The resource is added by the [`History1dPlugin`](crate::History1dPlugin)
+/// and can be edited at runtime.
+#[derive(Debug, Resource)]
+pub struct SpaceTimeHistory<S, const R: i32 = 1> {
+    /// Size of a history cell, the translation of a recorded cell is its
+    /// coordinate times the size on the `x` axis and its generation times the
+    /// negative size on the `y` axis.
+    pub cell_size: f32,
+    /// Maximum amount of recorded rows, older rows are despawned. If not set
+    /// every generation is kept.
+    pub max_rows: Option<u64>,
+    phantom: PhantomData<S>,
+}
+
+impl<S, const R: i32> SpaceTimeHistory<S, R> {
+    /// Instantiates new history settings with the given `cell_size`, keeping
+    /// every generation
+    #[must_use]
+    #[inline]
+    pub const fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            max_rows: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the maximum amount of recorded rows
+    #[must_use]
+    #[inline]
+    pub const fn with_max_rows(mut self, max_rows: u64) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+}
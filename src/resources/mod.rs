@@ -1,5 +1,15 @@
+#[cfg(feature = "1D")]
+pub use history::*;
 pub use map::*;
+pub use sandpile::*;
 pub use simulation::*;
+pub use state_map::*;
+pub use unbounded::*;
 
+#[cfg(feature = "1D")]
+mod history;
 mod map;
+mod sandpile;
 mod simulation;
+mod state_map;
+mod unbounded;
@@ -0,0 +1,59 @@
+use bevy::prelude::Resource;
+use std::marker::PhantomData;
+
+/// Resource to insert to relax the [`SandpilePlugin`](crate::SandpilePlugin)
+/// pile matching the `C` (`Cell`) type to stability every simulation tick.
+///
+/// Instead of a single toppling generation per tick, generations are computed
+/// until no cell topples, so every avalanche starts and ends within one frame.
+/// The resource replaces the [`SimulationTickBudget`](crate::SimulationTickBudget)
+/// for the pile, a [`SimulationStep`](crate::SimulationStep) relaxes a paused
+/// pile.
+#[derive(Debug, Resource)]
+pub struct SandpileRelaxation<C> {
+    /// Maximum amount of generations computed every tick, if not set the pile
+    /// is always relaxed to stability
+    pub max_generations: Option<u32>,
+    phantom: PhantomData<C>,
+}
+
+impl<C> SandpileRelaxation<C> {
+    /// Instantiates a relaxation to stability, without generation limit
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_generations: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the maximum amount of generations computed every tick
+    #[must_use]
+    #[inline]
+    pub const fn with_max_generations(mut self, max_generations: u32) -> Self {
+        self.max_generations = Some(max_generations);
+        self
+    }
+
+    /// Should another generation be computed after `generations`
+    #[must_use]
+    #[inline]
+    pub(crate) fn should_continue(self, generations: u32) -> bool {
+        self.max_generations.is_none_or(|max| generations < max)
+    }
+}
+
+impl<C> Default for SandpileRelaxation<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for SandpileRelaxation<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for SandpileRelaxation<C> {}
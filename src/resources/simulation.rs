@@ -1,11 +1,338 @@
-use bevy::prelude::Resource;
+use crate::{components::Cell, resources::CellStateMap};
+use bevy::{platform::time::Instant, prelude::Resource};
+use std::{
+    hash::{BuildHasher, RandomState},
+    marker::PhantomData,
+    num::NonZeroU32,
+    time::Duration,
+};
 
 /// Resource to insert for parallel queries and batching
 #[derive(Debug, Copy, Clone, Resource, Default)]
 #[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
 pub struct SimulationBatch;
 
+/// Resource to insert to seed the random streams given to stochastic cell
+/// states through [`CellContext::rng`](crate::CellContext::rng).
+///
+/// Every cell gets its own stream for every generation, derived from the seed,
+/// its coordinates and the generation, so simulations are reproducible with or
+/// without [`SimulationBatch`]. Without this resource the seed is `0`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Resource)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct SimulationRng {
+    seed: u64,
+}
+
+impl SimulationRng {
+    /// Instantiates a new random source from the given `seed`
+    #[must_use]
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Instantiates a new random source with a random seed
+    #[must_use]
+    pub fn from_entropy() -> Self {
+        Self::new(RandomState::new().hash_one(Instant::now()))
+    }
+
+    /// Retrieves the seed
+    #[must_use]
+    #[inline]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the seed
+    #[inline]
+    pub const fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
+
+/// Resource to insert to run several generations every simulation tick, to
+/// fast-forward the simulation beyond the frame rate.
+///
+/// State changes are applied between generations directly in the
+/// [`CellStateMap`](crate::CellStateMap), only the final cell states are
+/// inserted in the cell entities.
+///
+/// The resource has no effect on a paused simulation (see [`SimulationStep`])
+#[derive(Debug, Copy, Clone, Resource)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum SimulationTickBudget {
+    /// Runs the given amount of generations every tick, at least one
+    Generations(NonZeroU32),
+    /// Runs as many generations as fit in the given duration every tick, at
+    /// least one generation is always computed.
+    Duration(Duration),
+}
+
 /// Resource to insert to pause the cellular automaton simulation
 #[derive(Debug, Copy, Clone, Resource, Default)]
 #[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
 pub struct SimulationPause;
+
+/// Resource to insert to advance a paused simulation by the given amount of
+/// generations, one generation per simulation tick.
+///
+/// Inserting or modifying the resource requests new steps, every
+/// [`CellularAutomatonPlugin`](crate::CellularAutomatonPlugin) advances its
+/// own simulation. The resource has no effect if the simulation is not paused
+/// (see [`SimulationPause`]).
+#[derive(Debug, Copy, Clone, Resource)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct SimulationStep(pub u32);
+
+impl Default for SimulationStep {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Resource storing the current generation of the
+/// [`CellularAutomatonPlugin`](crate::CellularAutomatonPlugin) simulation
+/// matching the `C` (`Cell`) and `S` (`CellState`) types.
+///
+/// The generation is incremented every time the cells are updated, pausing the
+/// simulation will freeze the generation.
+#[derive(Debug, Resource)]
+pub struct SimulationGeneration<C, S> {
+    generation: u64,
+    phantom: PhantomData<(C, S)>,
+}
+
+impl<C, S> SimulationGeneration<C, S> {
+    /// Retrieves the current generation
+    #[must_use]
+    #[inline]
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Increments the generation, returning the new value
+    pub(crate) const fn advance(&mut self) -> u64 {
+        self.generation += 1;
+        self.generation
+    }
+}
+
+impl<C, S> Default for SimulationGeneration<C, S> {
+    fn default() -> Self {
+        Self {
+            generation: 0,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<C, S> Clone for SimulationGeneration<C, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, S> Copy for SimulationGeneration<C, S> {}
+
+/// Resource defining the tick rate of the
+/// [`CellularAutomatonPlugin`](crate::CellularAutomatonPlugin) simulation
+/// matching the `C` (`Cell`) and `S` (`CellState`) types.
+///
+/// The resource is added by the plugin with its
+/// [`tick_time_step`](crate::CellularAutomatonPlugin::tick_time_step) value
+/// and can be edited at runtime.
+#[derive(Debug, Resource)]
+pub struct SimulationSpeed<C, S> {
+    interval: Option<Duration>,
+    phantom: PhantomData<(C, S)>,
+}
+
+impl<C, S> SimulationSpeed<C, S> {
+    /// The simulation will tick every frame
+    #[must_use]
+    #[inline]
+    pub const fn every_frame() -> Self {
+        Self {
+            interval: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The simulation will tick every `interval`
+    #[must_use]
+    #[inline]
+    pub const fn from_interval(interval: Duration) -> Self {
+        Self {
+            interval: Some(interval),
+            phantom: PhantomData,
+        }
+    }
+
+    /// The simulation will tick `generations` times per second
+    ///
+    /// # Panics
+    ///
+    /// Panics if `generations` is not strictly positive and finite
+    #[must_use]
+    #[inline]
+    pub fn from_generations_per_second(generations: f64) -> Self {
+        Self::from_interval(Duration::from_secs_f64(generations.recip()))
+    }
+
+    /// Retrieves the tick interval, `None` meaning every frame
+    #[must_use]
+    #[inline]
+    pub const fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// Retrieves the amount of ticks per second, `None` meaning every frame
+    #[must_use]
+    #[inline]
+    pub fn generations_per_second(&self) -> Option<f64> {
+        self.interval.map(|i| i.as_secs_f64().recip())
+    }
+
+    /// Sets the tick interval, `None` meaning every frame
+    #[inline]
+    pub const fn set_interval(&mut self, interval: Option<Duration>) {
+        self.interval = interval;
+    }
+
+    /// Sets the amount of ticks per second
+    ///
+    /// # Panics
+    ///
+    /// Panics if `generations` is not strictly positive and finite
+    #[inline]
+    pub fn set_generations_per_second(&mut self, generations: f64) {
+        self.interval = Some(Duration::from_secs_f64(generations.recip()));
+    }
+}
+
+impl<C, S> Default for SimulationSpeed<C, S> {
+    fn default() -> Self {
+        Self::every_frame()
+    }
+}
+
+impl<C, S> Clone for SimulationSpeed<C, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, S> Copy for SimulationSpeed<C, S> {}
+
+/// Handling of the missing neighbors of a cell, see [`BoundaryCondition`].
+///
+/// A neighbor is missing when its coordinates don't match any cell, like for
+/// the cells at the edges of a finite world.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum Boundary<S> {
+    /// Missing neighbors are ignored, the rules will receive fewer neighbor
+    /// states for edge cells
+    #[default]
+    Ignore,
+    /// Missing neighbors have a fixed state, like a *dead* state
+    Fixed(S),
+    /// Missing neighbors copy the state of the evaluated cell. Every missing
+    /// neighbor of a cell gets the same state, regardless of its position, this
+    /// is not a mirror of the world at its edges, see [`Self::Mirror`]
+    CopyCell,
+    /// Missing neighbors mirror the world through its edges, getting the state
+    /// of the cell symmetric to them across the edge, the edge cells are not
+    /// duplicated.
+    ///
+    /// The mirror is computed by
+    /// [`Cell::mirrored_coordinates`](crate::Cell::mirrored_coordinates), the
+    /// neighbors which can't be mirrored, like on hexagonal cells or through
+    /// holes of the world, copy the state of the evaluated cell.
+    Mirror,
+}
+
+impl<S: Default> Boundary<S> {
+    /// Missing neighbors have the default state, usually the *dead* state
+    #[must_use]
+    #[inline]
+    pub fn dead() -> Self {
+        Self::Fixed(S::default())
+    }
+}
+
+impl<S> Boundary<S> {
+    /// Retrieves the state of a missing neighbor of a cell with `cell_state`
+    #[must_use]
+    #[inline]
+    pub const fn missing_neighbor_state<'a>(&'a self, cell_state: &'a S) -> Option<&'a S> {
+        match self {
+            Self::Ignore => None,
+            Self::Fixed(state) => Some(state),
+            Self::CopyCell | Self::Mirror => Some(cell_state),
+        }
+    }
+}
+
+/// Resource defining how missing neighbors are handled by the
+/// [`CellularAutomatonPlugin`](crate::CellularAutomatonPlugin) simulation
+/// matching the `C` (`Cell`) and `S` (`CellState`) types.
+///
+/// The resource is initialized from
+/// [`CellularAutomatonPlugin::with_boundary`](crate::CellularAutomatonPlugin::with_boundary)
+/// and can be edited at runtime, the cells with missing neighbors are then
+/// evaluated again in the next generation.
+#[derive(Debug, Resource)]
+pub struct BoundaryCondition<C, S> {
+    /// The missing neighbor handling
+    pub boundary: Boundary<S>,
+    phantom: PhantomData<C>,
+}
+
+impl<C, S> BoundaryCondition<C, S> {
+    /// Instantiates a new boundary condition
+    #[must_use]
+    #[inline]
+    pub const fn new(boundary: Boundary<S>) -> Self {
+        Self {
+            boundary,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: Cell, S> BoundaryCondition<C, S> {
+    /// Retrieves the state of the missing `neighbor` coordinates of `cell`
+    /// with `cell_state`, in the world defined by `map`
+    #[must_use]
+    pub fn missing_neighbor_state<'a>(
+        &'a self,
+        (cell, cell_state): (&C, &'a S),
+        neighbor: &C::Coordinates,
+        map: &'a CellStateMap<C, S>,
+    ) -> Option<&'a S> {
+        if matches!(self.boundary, Boundary::Mirror) {
+            let mirrored = cell
+                .mirrored_coordinates(neighbor, |c| map.get_entity(c).is_some())
+                .and_then(|c| map.get_state(&c));
+            if mirrored.is_some() {
+                return mirrored;
+            }
+        }
+        self.boundary.missing_neighbor_state(cell_state)
+    }
+}
+
+impl<C, S> Default for BoundaryCondition<C, S> {
+    fn default() -> Self {
+        Self::new(Boundary::Ignore)
+    }
+}
+
+impl<C, S: Clone> Clone for BoundaryCondition<C, S> {
+    fn clone(&self) -> Self {
+        Self::new(self.boundary.clone())
+    }
+}
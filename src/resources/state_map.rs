@@ -0,0 +1,293 @@
+use crate::components::Cell;
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::{Entity, Resource},
+};
+
+#[derive(Debug, Clone)]
+struct CellEntry<C, S> {
+    entity: Entity,
+    cell: C,
+    state: S,
+}
+
+/// Global cell state container resource, storing the current state of every
+/// cell by coordinates.
+///
+/// The resource is automatically added and incrementally updated by the
+/// [`CellularAutomatonPlugin`](crate::CellularAutomatonPlugin): only spawned,
+/// despawned and changed cells are processed, so the simulation doesn't need
+/// to rebuild the entire world state every tick.
+///
+/// The map also tracks *active* cells: the cells whose own state or one of
+/// their neighbor states changed since the last generation, and which are the
+/// only ones evaluated for [`CellState::NEIGHBOR_DRIVEN`] rules.
+///
+/// The neighbor weights of the `C` cells, given by [`Cell::neighbor_weights`],
+/// are computed once when the map is created.
+///
+/// [`CellState::NEIGHBOR_DRIVEN`]: crate::CellState::NEIGHBOR_DRIVEN
+#[derive(Debug, Clone, Resource)]
+pub struct CellStateMap<C: Cell, S> {
+    cells: HashMap<C::Coordinates, CellEntry<C, S>>,
+    entities: HashMap<Entity, C::Coordinates>,
+    active: HashSet<C::Coordinates>,
+    neighbor_weights: Option<Vec<f32>>,
+}
+
+impl<C: Cell, S> Default for CellStateMap<C, S> {
+    fn default() -> Self {
+        Self {
+            cells: Default::default(),
+            entities: Default::default(),
+            active: Default::default(),
+            neighbor_weights: C::neighbor_weights(),
+        }
+    }
+}
+
+impl<C: Cell, S> CellStateMap<C, S> {
+    /// Retrieves a cell state using its `coordinates`
+    #[must_use]
+    #[inline]
+    pub fn get_state(&self, coordinates: &C::Coordinates) -> Option<&S> {
+        self.cells.get(coordinates).map(|entry| &entry.state)
+    }
+
+    /// Retrieves a cell entity using its `coordinates`
+    #[must_use]
+    #[inline]
+    pub fn get_entity(&self, coordinates: &C::Coordinates) -> Option<Entity> {
+        self.cells.get(coordinates).map(|entry| entry.entity)
+    }
+
+    /// Iterates through every stored cell coordinates and state
+    pub fn iter(&self) -> impl Iterator<Item = (&C::Coordinates, &S)> {
+        self.cells
+            .iter()
+            .map(|(coords, entry)| (coords, &entry.state))
+    }
+
+    /// Returns the number of stored cells
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if the map contains no cell
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Iterates through the coordinates of the cells to evaluate in the next
+    /// generation.
+    ///
+    /// # Note
+    ///
+    /// Some coordinates may not match any stored cell, as the neighbors of
+    /// changed cells are always considered active.
+    pub fn active_cells(&self) -> impl Iterator<Item = &C::Coordinates> {
+        self.active.iter()
+    }
+
+    /// Is the cell at `coordinates` to be evaluated in the next generation
+    #[must_use]
+    #[inline]
+    pub fn is_active(&self, coordinates: &C::Coordinates) -> bool {
+        self.active.contains(coordinates)
+    }
+
+    /// Retrieves the neighbor weights of the cells, see
+    /// [`Cell::neighbor_weights`]
+    #[inline]
+    pub(crate) fn neighbor_weights(&self) -> Option<&[f32]> {
+        self.neighbor_weights.as_deref()
+    }
+
+    /// Retrieves a cell and its state using its `coordinates`
+    #[inline]
+    pub(crate) fn get_cell_state(&self, coordinates: &C::Coordinates) -> Option<(&C, &S)> {
+        self.cells
+            .get(coordinates)
+            .map(|entry| (&entry.cell, &entry.state))
+    }
+
+    /// Retrieves a cell and its state using its `entity`
+    #[inline]
+    pub(crate) fn get_entity_cell_state(&self, entity: Entity) -> Option<(&C, &S)> {
+        self.entities
+            .get(&entity)
+            .and_then(|coords| self.get_cell_state(coords))
+    }
+
+    /// Iterates through every stored cell and state
+    pub(crate) fn cell_states(&self) -> impl Iterator<Item = (&C, &S)> {
+        self.cells.values().map(|entry| (&entry.cell, &entry.state))
+    }
+
+    /// Marks `cell` and all its neighbors as active
+    pub(crate) fn mark_active(&mut self, cell: &C) {
+        self.active.insert(cell.coords().clone());
+        self.active.extend(cell.neighbor_coordinates());
+    }
+
+    /// Marks the cell at `coordinates` as active, without its neighbors, for
+    /// cells whose state depends on time
+    pub(crate) fn mark_pending(&mut self, coordinates: C::Coordinates) {
+        self.active.insert(coordinates);
+    }
+
+    /// Marks the cells with missing neighbors as active, without their
+    /// neighbors, as their evaluation depends on the boundary condition
+    pub(crate) fn mark_edges_active(&mut self) {
+        let edges: Vec<_> = self
+            .cells
+            .iter()
+            .filter(|(_, entry)| {
+                entry
+                    .cell
+                    .neighbor_coordinates()
+                    .any(|c| !self.cells.contains_key(&c))
+            })
+            .map(|(coords, _)| coords.clone())
+            .collect();
+        self.active.extend(edges);
+    }
+
+    /// Retrieves and clears the active cell coordinates
+    pub(crate) fn take_active(&mut self) -> HashSet<C::Coordinates> {
+        std::mem::take(&mut self.active)
+    }
+
+    /// Updates the state at `coordinates`, marking the cell and its neighbors
+    /// as active.
+    ///
+    /// # Returns
+    ///
+    /// The matching cell entity, if the `coordinates` are stored
+    pub(crate) fn set_state(&mut self, coordinates: &C::Coordinates, state: S) -> Option<Entity> {
+        let entry = self.cells.get_mut(coordinates)?;
+        entry.state = state;
+        let (entity, cell) = (entry.entity, entry.cell.clone());
+        self.mark_active(&cell);
+        Some(entity)
+    }
+
+    /// Removes `entity` from the map, marking its neighbors as active.
+    ///
+    /// # Returns
+    ///
+    /// The entity coordinates if it was present
+    pub(crate) fn remove_entity(&mut self, entity: Entity) -> Option<C::Coordinates> {
+        let coords = self.entities.remove(&entity)?;
+        self.remove_coordinates(&coords, entity);
+        Some(coords)
+    }
+
+    /// Removes the cell at `coordinates` only if it belongs to `entity`, as
+    /// another cell may have replaced it
+    fn remove_coordinates(&mut self, coordinates: &C::Coordinates, entity: Entity) {
+        if self.get_entity(coordinates) == Some(entity) {
+            if let Some(entry) = self.cells.remove(coordinates) {
+                self.mark_active(&entry.cell);
+            }
+        }
+    }
+}
+
+impl<C: Cell, S: PartialEq> CellStateMap<C, S> {
+    /// Stores the `state` of the `entity` cell, removing any previous entry
+    /// for `entity` at different coordinates.
+    ///
+    /// If the cell is new or its state changed, the cell and its neighbors are
+    /// marked as active
+    pub(crate) fn insert(&mut self, entity: Entity, cell: &C, state: S) {
+        let coords = cell.coords().clone();
+        if let Some(old_coords) = self.entities.insert(entity, coords.clone()) {
+            if old_coords != coords {
+                self.remove_coordinates(&old_coords, entity);
+            }
+        }
+        let changed = self
+            .cells
+            .get(&coords)
+            .is_none_or(|entry| entry.entity != entity || entry.state != state);
+        if changed {
+            self.mark_active(cell);
+        }
+        self.cells.insert(
+            coords,
+            CellEntry {
+                entity,
+                cell: cell.clone(),
+                state,
+            },
+        );
+    }
+}
+
+#[cfg(all(test, feature = "2D"))]
+mod tests {
+    use super::*;
+    use crate::{ConwayCellState, MooreCell2d};
+    use bevy::prelude::IVec2;
+
+    type Map = CellStateMap<MooreCell2d, ConwayCellState>;
+
+    #[test]
+    fn moved_entity() {
+        let mut map = Map::default();
+        let entity = Entity::from_raw_u32(1).unwrap();
+        map.insert(
+            entity,
+            &MooreCell2d::new(IVec2::ZERO),
+            ConwayCellState(true),
+        );
+        map.insert(
+            entity,
+            &MooreCell2d::new(IVec2::ONE),
+            ConwayCellState(false),
+        );
+        assert_eq!(map.len(), 1);
+        assert!(map.get_state(&IVec2::ZERO).is_none());
+        assert_eq!(map.get_state(&IVec2::ONE), Some(&ConwayCellState(false)));
+    }
+
+    #[test]
+    fn replaced_entity() {
+        let mut map = Map::default();
+        let old_entity = Entity::from_raw_u32(1).unwrap();
+        let new_entity = Entity::from_raw_u32(2).unwrap();
+        let cell = MooreCell2d::new(IVec2::ZERO);
+        map.insert(old_entity, &cell, ConwayCellState(true));
+        map.insert(new_entity, &cell, ConwayCellState(false));
+        assert_eq!(map.remove_entity(old_entity), Some(IVec2::ZERO));
+        assert_eq!(map.get_entity(&IVec2::ZERO), Some(new_entity));
+        assert_eq!(map.remove_entity(new_entity), Some(IVec2::ZERO));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn active_cells() {
+        let mut map = Map::default();
+        let entity = Entity::from_raw_u32(1).unwrap();
+        let cell = MooreCell2d::new(IVec2::ZERO);
+        map.insert(entity, &cell, ConwayCellState(true));
+        assert_eq!(map.take_active().len(), 9);
+        // Same state
+        map.insert(entity, &cell, ConwayCellState(true));
+        assert_eq!(map.active_cells().count(), 0);
+        // Changed state
+        map.insert(entity, &cell, ConwayCellState(false));
+        assert!(map.is_active(&IVec2::ZERO));
+        assert!(map.is_active(&IVec2::ONE));
+        assert!(!map.is_active(&IVec2::splat(2)));
+        map.take_active();
+        // Removed cell
+        map.remove_entity(entity);
+        assert_eq!(map.active_cells().count(), 9);
+    }
+}
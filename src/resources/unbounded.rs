@@ -0,0 +1,101 @@
+use crate::components::{Cell, CellState};
+use bevy::{ecs::system::EntityCommands, prelude::Resource};
+
+/// Resource to insert to run an unbounded simulation, where cells are spawned
+/// and despawned on demand instead of being limited to the existing entities.
+///
+/// Every simulation generation, the coordinates without a cell next to the
+/// changed cells are evaluated from the *dead* state, and a new cell entity is
+/// spawned if their state changes. Cells returning to the *dead* state are
+/// despawned.
+///
+/// # Note
+///
+/// Missing neighbors are handled according to the
+/// [`BoundaryCondition`](crate::BoundaryCondition), the default behavior of
+/// ignoring them is equivalent to *dead* neighbors for most rules.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # #[cfg(feature = "2D")]
+/// # use bevy_life::{ConwayCellState, MooreCell2d, UnboundedWorld};
+/// # #[cfg(feature = "2D")]
+/// fn setup(mut commands: Commands) {
+///     commands.insert_resource(
+///         UnboundedWorld::<MooreCell2d, ConwayCellState>::new(MooreCell2d::new).with_spawn(
+///             |entity, cell, state| {
+///                 let translation = cell.coords.as_vec2().extend(0.0) * 10.0;
+///                 entity.insert((cell, state, Transform::from_translation(translation)));
+///             },
+///         ),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Resource)]
+pub struct UnboundedWorld<C: Cell, S> {
+    dead_state: S,
+    new_cell: fn(C::Coordinates) -> C,
+    spawn: fn(&mut EntityCommands, C, S),
+}
+
+impl<C: Cell, S: CellState> UnboundedWorld<C, S> {
+    /// Instantiates a new unbounded world using the default state as the
+    /// *dead* state.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_cell` - Function building a cell from its coordinates
+    #[must_use]
+    pub fn new(new_cell: fn(C::Coordinates) -> C) -> Self
+    where
+        S: Default,
+    {
+        Self {
+            dead_state: S::default(),
+            new_cell,
+            spawn: |entity, cell, state| {
+                entity.insert((cell, state));
+            },
+        }
+    }
+
+    /// Sets the *dead* state, the state of cells to despawn
+    #[must_use]
+    #[inline]
+    pub fn with_dead_state(mut self, dead_state: S) -> Self {
+        self.dead_state = dead_state;
+        self
+    }
+
+    /// Sets the `spawn` hook, inserting the cell and its state on a new cell
+    /// entity along with any custom component (sprites, meshes, etc).
+    ///
+    /// By default only the cell and its state are inserted.
+    #[must_use]
+    #[inline]
+    pub fn with_spawn(mut self, spawn: fn(&mut EntityCommands, C, S)) -> Self {
+        self.spawn = spawn;
+        self
+    }
+
+    /// Retrieves the *dead* state
+    #[must_use]
+    #[inline]
+    pub const fn dead_state(&self) -> &S {
+        &self.dead_state
+    }
+
+    /// Builds a new cell at `coordinates`
+    #[inline]
+    pub(crate) fn new_cell(&self, coordinates: C::Coordinates) -> C {
+        (self.new_cell)(coordinates)
+    }
+
+    /// Inserts `cell` and its `state` on a new cell `entity`
+    #[inline]
+    pub(crate) fn spawn(&self, entity: &mut EntityCommands, cell: C, state: S) {
+        (self.spawn)(entity, cell, state);
+    }
+}
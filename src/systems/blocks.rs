@@ -0,0 +1,248 @@
+use crate::{
+    components::{BlockRule, MooreCell2d, BLOCK_OFFSETS},
+    messages::GenerationAdvanced,
+    resources::{CellStateMap, SimulationGeneration},
+    systems::cells::SimulationControl,
+    SimulationBatch,
+};
+use bevy::{
+    platform::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    },
+    prelude::*,
+    tasks::{ComputeTaskPool, ParallelSlice, TaskPool},
+};
+
+/// Retrieves the origin of the block containing `coords`, for the block
+/// partition of `generation`
+fn block_origin(coords: IVec2, generation: u64) -> IVec2 {
+    let offset = if generation.is_multiple_of(2) {
+        IVec2::ZERO
+    } else {
+        IVec2::ONE
+    };
+    (coords - offset).div_euclid(IVec2::splat(2)) * 2 + offset
+}
+
+/// Computes a new generation of blocks in `map`, returning the changed cell
+/// states.
+///
+/// With a [`BlockRule::CHANGE_DRIVEN`] rule, only the blocks containing cells
+/// activated during the current or `previous` generation are evaluated, the
+/// current active cells replacing the `previous` ones.
+fn next_block_generation<R: BlockRule>(
+    map: &mut CellStateMap<MooreCell2d, R::State>,
+    previous: &mut HashSet<IVec2>,
+    generation: u64,
+    batch: bool,
+) -> Vec<(Entity, R::State)> {
+    let active = map.take_active();
+    let origins: Vec<_> = if R::CHANGE_DRIVEN {
+        let origins: HashSet<_> = active
+            .iter()
+            .chain(previous.iter())
+            .map(|coords| block_origin(*coords, generation))
+            .collect();
+        *previous = active;
+        origins.into_iter().collect()
+    } else {
+        let origins: HashSet<_> = map
+            .iter()
+            .map(|(coords, _)| block_origin(*coords, generation))
+            .collect();
+        origins.into_iter().collect()
+    };
+    let map_ref = &*map;
+    let handle_block = |origin: IVec2| {
+        let states = BLOCK_OFFSETS.map(|offset| map_ref.get_state(&(origin + offset)));
+        let block = states.map(|state| state.cloned().unwrap_or_default());
+        let new_block = R::new_block_state(block, generation);
+        BLOCK_OFFSETS
+            .into_iter()
+            .zip(states)
+            .zip(new_block)
+            .filter_map(move |((offset, state), new_state)| {
+                (state? != &new_state).then_some((origin + offset, new_state))
+            })
+    };
+    let changes: Vec<_> = if batch {
+        let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+        origins
+            .par_splat_map(pool, None, |_, chunk| {
+                chunk
+                    .iter()
+                    .flat_map(|&origin| handle_block(origin))
+                    .collect::<Vec<_>>()
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    } else {
+        origins.into_iter().flat_map(handle_block).collect()
+    };
+    changes
+        .into_iter()
+        .filter_map(|(coords, new_state)| {
+            let entity = map.set_state(&coords, new_state.clone())?;
+            Some((entity, new_state))
+        })
+        .collect()
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn handle_blocks<R: BlockRule>(
+    mut states: Query<&mut R::State, With<MooreCell2d>>,
+    mut map: ResMut<CellStateMap<MooreCell2d, R::State>>,
+    mut generation: ResMut<SimulationGeneration<MooreCell2d, R::State>>,
+    mut generation_messages: MessageWriter<GenerationAdvanced<MooreCell2d, R::State>>,
+    mut control: SimulationControl,
+    mut previous: Local<HashSet<IVec2>>,
+    batch: Option<Res<SimulationBatch>>,
+) {
+    if !control.should_run() {
+        return;
+    }
+    let start = Instant::now();
+    let mut generations = 0;
+    let mut new_states = HashMap::new();
+    loop {
+        let changes = next_block_generation::<R>(
+            &mut map,
+            &mut previous,
+            generation.generation(),
+            batch.is_some(),
+        );
+        generations += 1;
+        let generation = generation.advance();
+        generation_messages.write(GenerationAdvanced::new(generation, changes.len()));
+        new_states.extend(changes);
+        let stable = R::CHANGE_DRIVEN && previous.is_empty() && map.active_cells().next().is_none();
+        if stable || !control.should_continue(generations, start) {
+            break;
+        }
+    }
+    for (entity, new_state) in new_states {
+        if let Ok(mut state) = states.get_mut(entity) {
+            state.set_if_neq(new_state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockCellularAutomatonPlugin, ConwayCellState, SimulationTickBudget, TronRule};
+    use std::num::NonZeroU32;
+
+    /// Every block rotates a quarter turn counterclockwise
+    struct RotationRule;
+
+    impl BlockRule for RotationRule {
+        type State = ConwayCellState;
+
+        fn new_block_state(block: [Self::State; 4], _generation: u64) -> [Self::State; 4] {
+            let [bottom_left, bottom_right, top_left, top_right] = block;
+            [bottom_right, top_right, bottom_left, top_left]
+        }
+    }
+
+    #[test]
+    fn block_partition() {
+        assert_eq!(block_origin(IVec2::new(3, 2), 0), IVec2::new(2, 2));
+        assert_eq!(block_origin(IVec2::new(-1, 0), 0), IVec2::new(-2, 0));
+        assert_eq!(block_origin(IVec2::new(3, 2), 1), IVec2::new(3, 1));
+        assert_eq!(block_origin(IVec2::new(0, 0), 1), IVec2::new(-1, -1));
+    }
+
+    fn alive_cells(app: &mut App) -> Vec<IVec2> {
+        let mut alive: Vec<_> = app
+            .world_mut()
+            .query::<(&MooreCell2d, &ConwayCellState)>()
+            .iter(app.world())
+            .filter(|(_, state)| state.0)
+            .map(|(cell, _)| cell.coords)
+            .collect();
+        alive.sort_by_key(|coords| (coords.x, coords.y));
+        alive
+    }
+
+    fn tron_alternating_blocks(batch: bool) {
+        let mut app = App::new();
+        app.add_plugins(BlockCellularAutomatonPlugin::<TronRule>::default());
+        if batch {
+            app.insert_resource(SimulationBatch);
+        }
+        for x in 0..4 {
+            for y in 0..4 {
+                app.world_mut()
+                    .spawn((MooreCell2d::new(IVec2::new(x, y)), ConwayCellState(false)));
+            }
+        }
+        // Every block is uniform
+        app.update();
+        assert_eq!(alive_cells(&mut app).len(), 16);
+        // Shifted blocks, only the central block is complete and uniform
+        app.update();
+        let mut expected = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                if !(1..3).contains(&x) || !(1..3).contains(&y) {
+                    expected.push(IVec2::new(x, y));
+                }
+            }
+        }
+        assert_eq!(alive_cells(&mut app), expected);
+        let generation = app
+            .world()
+            .resource::<SimulationGeneration<MooreCell2d, ConwayCellState>>();
+        assert_eq!(generation.generation(), 2);
+    }
+
+    #[test]
+    fn tron() {
+        tron_alternating_blocks(false);
+    }
+
+    #[test]
+    fn tron_batch() {
+        tron_alternating_blocks(true);
+    }
+
+    #[test]
+    fn stable_blocks() {
+        let mut app = App::new();
+        app.add_plugins(BlockCellularAutomatonPlugin::<RotationRule>::default())
+            .insert_resource(SimulationTickBudget::Generations(
+                NonZeroU32::new(50).unwrap(),
+            ));
+        for x in 0..4 {
+            for y in 0..4 {
+                app.world_mut()
+                    .spawn((MooreCell2d::new(IVec2::new(x, y)), ConwayCellState(false)));
+            }
+        }
+        let generation = |app: &App| {
+            app.world()
+                .resource::<SimulationGeneration<MooreCell2d, ConwayCellState>>()
+                .generation()
+        };
+        // The empty grid is stable once both block partitions are evaluated
+        app.update();
+        assert_eq!(generation(&app), 2);
+        app.update();
+        assert_eq!(generation(&app), 3);
+        // A live cell rotates in its block, the budget is used
+        let cell = app
+            .world_mut()
+            .query::<(&MooreCell2d, &mut ConwayCellState)>()
+            .iter_mut(app.world_mut())
+            .find(|(cell, _)| cell.coords == IVec2::ONE)
+            .unwrap()
+            .1;
+        *cell.into_inner() = ConwayCellState(true);
+        app.update();
+        assert_eq!(generation(&app), 53);
+        assert_eq!(alive_cells(&mut app).len(), 1);
+    }
+}
@@ -1,57 +1,353 @@
 use crate::{
-    components::{Cell, CellState},
-    resources::CellMap,
-    SimulationBatch, SimulationPause,
+    components::{Cell, CellContext, CellState},
+    messages::GenerationAdvanced,
+    resources::{
+        BoundaryCondition, CellMap, CellStateMap, SimulationGeneration, SimulationRng,
+        SimulationSpeed, UnboundedWorld,
+    },
+    SimulationBatch, SimulationPause, SimulationStep, SimulationTickBudget,
 };
-use bevy::{log, platform::collections::HashMap, prelude::*};
+use bevy::{
+    ecs::{change_detection::Tick, system::SystemParam},
+    log,
+    platform::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    },
+    prelude::*,
+    tasks::{ComputeTaskPool, ParallelSlice, TaskPool},
+};
+use std::time::Duration;
+
+/// Simulation pause, step and budget state
+#[derive(SystemParam)]
+pub struct SimulationControl<'w, 's> {
+    pause: Option<Res<'w, SimulationPause>>,
+    step: Option<Res<'w, SimulationStep>>,
+    budget: Option<Res<'w, SimulationTickBudget>>,
+    /// Change tick of the last handled [`SimulationStep`] and the amount of
+    /// generations already advanced for it
+    consumed_steps: Local<'s, Option<(Tick, u32)>>,
+}
+
+impl SimulationControl<'_, '_> {
+    /// Should the simulation advance a generation
+    pub fn should_run(&mut self) -> bool {
+        if self.pause.is_none() {
+            return true;
+        }
+        let Some(step) = &self.step else {
+            return false;
+        };
+        let tick = step.last_changed();
+        let consumed = match *self.consumed_steps {
+            Some((consumed_tick, consumed)) if consumed_tick == tick => consumed,
+            _ => 0,
+        };
+        if consumed >= step.0 {
+            return false;
+        }
+        *self.consumed_steps = Some((tick, consumed + 1));
+        true
+    }
+
+    /// Should the simulation advance another generation this tick, after
+    /// `generations` were computed since `start`
+    pub fn should_continue(&self, generations: u32, start: Instant) -> bool {
+        if self.pause.is_some() {
+            return false;
+        }
+        match self.budget.as_deref() {
+            None => false,
+            Some(SimulationTickBudget::Generations(max)) => generations < max.get(),
+            Some(SimulationTickBudget::Duration(duration)) => start.elapsed() < *duration,
+        }
+    }
+}
 
-fn handle_cell<C, S>((cell, state): (&C, &S), map: &HashMap<C::Coordinates, S>) -> Option<S>
+/// Shared data to compute the next state of cells for a generation
+struct GenerationEvaluation<'a, C: Cell, S> {
+    map: &'a CellStateMap<C, S>,
+    boundary: &'a BoundaryCondition<C, S>,
+    generation: u64,
+    delta: Duration,
+    seed: u64,
+}
+
+impl<C, S> GenerationEvaluation<'_, C, S>
 where
     C: Cell,
     S: CellState,
 {
-    let neighbor_states = cell.neighbor_coordinates().filter_map(|c| map.get(&c));
-    let new_state = state.new_cell_state(neighbor_states);
-    if &new_state == state {
-        None
-    } else {
-        Some(new_state)
+    fn handle_cell(&self, (cell, state): (&C, &S)) -> Option<CellEvaluation<C, S>> {
+        let neighbor_states = cell.neighbor_coordinates().enumerate().map(|(i, c)| {
+            let neighbor_state = self.map.get_state(&c).or_else(|| {
+                self.boundary
+                    .missing_neighbor_state((cell, state), &c, self.map)
+            });
+            (i, neighbor_state)
+        });
+        let context = CellContext::new(cell, self.generation, self.delta)
+            .with_seed(self.seed)
+            .with_neighbor_weights(self.map.neighbor_weights());
+        let new_state = state.new_cell_state_with_context(neighbor_states, &context);
+        let coords = cell.coords().clone();
+        if &new_state != state {
+            Some(CellEvaluation::Changed(coords, new_state))
+        } else if context.is_time_dependent() {
+            Some(CellEvaluation::Pending(coords))
+        } else {
+            None
+        }
     }
 }
 
+/// Evaluation of a cell which must be handled by the simulation
+enum CellEvaluation<C: Cell, S> {
+    /// The cell state changed
+    Changed(C::Coordinates, S),
+    /// The cell state is unchanged but depends on time, the cell must be
+    /// evaluated again in the next generation
+    Pending(C::Coordinates),
+}
+
+/// Run condition ticking the simulation timer according to the
+/// [`SimulationSpeed`]
 #[allow(clippy::needless_pass_by_value)]
+pub fn simulation_tick<C, S>(
+    time: Option<Res<Time>>,
+    speed: Res<SimulationSpeed<C, S>>,
+    mut timer: Local<Option<Timer>>,
+) -> bool
+where
+    C: Cell,
+    S: CellState,
+{
+    let Some(interval) = speed.interval() else {
+        return true;
+    };
+    let Some(time) = time else {
+        log::warn!("Missing `Time` resource for the simulation speed");
+        return false;
+    };
+    let timer = timer.get_or_insert_with(|| Timer::new(interval, TimerMode::Repeating));
+    if timer.duration() != interval {
+        timer.set_duration(interval);
+    }
+    timer.tick(time.delta()).just_finished()
+}
+
+/// Cell changes of a simulation generation
+struct GenerationChanges<C, S> {
+    /// Changed cell entities and their new states
+    states: Vec<(Entity, S)>,
+    /// Cell entities returning to the *dead* state of an [`UnboundedWorld`]
+    deaths: Vec<Entity>,
+    /// New cells of an [`UnboundedWorld`] and their states
+    births: Vec<(C, S)>,
+}
+
+impl<C, S> GenerationChanges<C, S> {
+    const fn len(&self) -> usize {
+        self.states.len() + self.deaths.len() + self.births.len()
+    }
+}
+
+/// Computes a new generation in `map`, returning the cell changes.
+///
+/// The `map` states are only updated once every cell is computed, acting as a
+/// double buffer. Dead cells are removed from the `map` but births are not
+/// inserted, as their entities must be spawned first.
+fn next_generation<C, S>(
+    map: &mut CellStateMap<C, S>,
+    boundary: &BoundaryCondition<C, S>,
+    unbounded: Option<&UnboundedWorld<C, S>>,
+    (generation, delta, seed): (u64, Duration, u64),
+    batch: bool,
+) -> GenerationChanges<C, S>
+where
+    C: Cell,
+    S: CellState,
+{
+    let active = map.take_active();
+    let map_ref = &*map;
+    let evaluation = GenerationEvaluation {
+        map: map_ref,
+        boundary,
+        generation,
+        delta,
+        seed,
+    };
+    let cells: Vec<_> = if S::NEIGHBOR_DRIVEN {
+        active
+            .iter()
+            .filter_map(|c| map_ref.get_cell_state(c))
+            .collect()
+    } else {
+        map_ref.cell_states().collect()
+    };
+    let changes: Vec<_> = if batch {
+        let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+        cells
+            .par_splat_map(pool, None, |_, chunk| {
+                chunk
+                    .iter()
+                    .filter_map(|&cell| evaluation.handle_cell(cell))
+                    .collect::<Vec<_>>()
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    } else {
+        cells
+            .into_iter()
+            .filter_map(|cell| evaluation.handle_cell(cell))
+            .collect()
+    };
+    let mut pending_births = Vec::new();
+    let births = unbounded.map_or_else(Vec::new, |world| {
+        active
+            .iter()
+            .filter(|coords| map_ref.get_entity(coords).is_none())
+            .filter_map(|coords| {
+                let cell = world.new_cell(coords.clone());
+                match evaluation.handle_cell((&cell, world.dead_state()))? {
+                    CellEvaluation::Changed(_, state) => Some((cell, state)),
+                    CellEvaluation::Pending(coords) => {
+                        pending_births.push(coords);
+                        None
+                    }
+                }
+            })
+            .collect()
+    });
+    for coords in pending_births {
+        map.mark_pending(coords);
+    }
+    let mut generation = GenerationChanges {
+        states: Vec::with_capacity(changes.len()),
+        deaths: Vec::new(),
+        births,
+    };
+    for change in changes {
+        let (coords, new_state) = match change {
+            CellEvaluation::Changed(coords, new_state) => (coords, new_state),
+            CellEvaluation::Pending(coords) => {
+                map.mark_pending(coords);
+                continue;
+            }
+        };
+        if unbounded.is_some_and(|world| &new_state == world.dead_state()) {
+            if let Some(entity) = map.get_entity(&coords) {
+                map.remove_entity(entity);
+                generation.deaths.push(entity);
+            }
+        } else if let Some(entity) = map.set_state(&coords, new_state.clone()) {
+            generation.states.push((entity, new_state));
+        }
+    }
+    generation
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 pub fn handle_cells<C, S>(
     mut commands: Commands,
-    par_commands: ParallelCommands,
-    query: Query<(Entity, &C, &S)>,
-    pause: Option<Res<SimulationPause>>,
+    mut states: Query<&mut S, With<C>>,
+    mut map: ResMut<CellStateMap<C, S>>,
+    mut generation: ResMut<SimulationGeneration<C, S>>,
+    mut generation_messages: MessageWriter<GenerationAdvanced<C, S>>,
+    mut control: SimulationControl,
+    boundary: Res<BoundaryCondition<C, S>>,
+    unbounded: Option<Res<UnboundedWorld<C, S>>>,
     batch: Option<Res<SimulationBatch>>,
+    speed: Res<SimulationSpeed<C, S>>,
+    time: Option<Res<Time>>,
+    rng: Option<Res<SimulationRng>>,
 ) where
     C: Cell,
     S: CellState,
 {
-    if pause.is_some() {
+    if boundary.is_changed() && !boundary.is_added() {
+        map.mark_edges_active();
+    }
+    if !control.should_run() {
         return;
     }
-    let map: HashMap<_, _> = query
-        .iter()
-        .map(|(_entity, cell, state)| (cell.coords().clone(), state.clone()))
-        .collect();
-    if batch.is_some() {
-        query.par_iter().for_each(|(entity, cell, state)| {
-            if let Some(new_state) = handle_cell((cell, state), &map) {
-                par_commands.command_scope(|mut cmd| {
-                    cmd.entity(entity).try_insert(new_state);
-                });
-            }
-        });
-    } else {
-        for (entity, cell, state) in query.iter() {
-            if let Some(new_state) = handle_cell((cell, state), &map) {
-                commands.entity(entity).try_insert(new_state);
+    let start = Instant::now();
+    let mut generations = 0;
+    let mut new_states = HashMap::new();
+    let mut spawned = HashSet::new();
+    let delta = speed
+        .interval()
+        .or_else(|| time.map(|time| time.delta()))
+        .unwrap_or_default();
+    let rng_seed = rng.map_or(0, |rng| rng.seed());
+    loop {
+        let changes = next_generation(
+            &mut map,
+            &boundary,
+            unbounded.as_deref(),
+            (generation.generation(), delta, rng_seed),
+            batch.is_some(),
+        );
+        generations += 1;
+        let generation = generation.advance();
+        generation_messages.write(GenerationAdvanced::new(generation, changes.len()));
+        new_states.extend(changes.states);
+        for entity in changes.deaths {
+            new_states.remove(&entity);
+            spawned.remove(&entity);
+            commands.entity(entity).despawn();
+        }
+        for (cell, state) in changes.births {
+            let entity = commands.spawn_empty().id();
+            map.insert(entity, &cell, state);
+            spawned.insert(entity);
+        }
+        let stable = S::NEIGHBOR_DRIVEN && map.active_cells().next().is_none();
+        if stable || !control.should_continue(generations, start) {
+            break;
+        }
+    }
+    if let Some(world) = unbounded {
+        for entity in spawned {
+            new_states.remove(&entity);
+            if let Some((cell, state)) = map.get_entity_cell_state(entity) {
+                world.spawn(&mut commands.entity(entity), cell.clone(), state.clone());
             }
         }
     }
+    for (entity, new_state) in new_states {
+        if let Ok(mut state) = states.get_mut(entity) {
+            state.set_if_neq(new_state);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn handle_new_states<C, S>(
+    query: Query<(Entity, &C, &S), Or<(Changed<C>, Changed<S>)>>,
+    mut map: ResMut<CellStateMap<C, S>>,
+) where
+    C: Cell,
+    S: CellState,
+{
+    for (entity, cell, state) in &query {
+        map.insert(entity, cell, state.clone());
+    }
+}
+
+pub fn handle_removed_states<C, S>(
+    mut removed_cells: RemovedComponents<C>,
+    mut removed_states: RemovedComponents<S>,
+    mut map: ResMut<CellStateMap<C, S>>,
+) where
+    C: Cell,
+    S: CellState,
+{
+    for entity in removed_cells.read().chain(removed_states.read()) {
+        map.remove_entity(entity);
+    }
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -84,3 +380,672 @@ where
     log::trace!("Removing {} cells from cell map", removed_cells.len());
     map.remove_entities(removed_cells.read());
 }
+
+#[cfg(all(test, feature = "2D"))]
+mod tests {
+    use crate::{
+        Boundary, BoundaryCondition, Cell, CellContext, CellState, CellStateMap,
+        CellularAutomatonPlugin, ConwayCellState, GameOfLife2dPlugin, GenerationAdvanced,
+        MooreCell2d, Neighborhood, RingNeighborhood, RingRangeCell2d, SimulationBatch,
+        SimulationGeneration, SimulationPause, SimulationRng, SimulationSpeed, SimulationStep,
+        SimulationTickBudget, ToroidalCell, UnboundedWorld,
+    };
+    use bevy::{
+        ecs::{message::Messages, schedule::ScheduleLabel},
+        prelude::*,
+    };
+    use std::{num::NonZeroU32, time::Duration};
+
+    type Generation = SimulationGeneration<MooreCell2d, ConwayCellState>;
+    type Speed = SimulationSpeed<MooreCell2d, ConwayCellState>;
+
+    const HORIZONTAL: [IVec2; 3] = [IVec2::new(1, 2), IVec2::new(2, 2), IVec2::new(3, 2)];
+    const VERTICAL: [IVec2; 3] = [IVec2::new(2, 1), IVec2::new(2, 2), IVec2::new(2, 3)];
+
+    fn spawn_board(app: &mut App, size: i32, alive: &[IVec2]) {
+        for x in 0..size {
+            for y in 0..size {
+                let coords = IVec2::new(x, y);
+                app.world_mut().spawn((
+                    MooreCell2d::new(coords),
+                    ConwayCellState(alive.contains(&coords)),
+                ));
+            }
+        }
+    }
+
+    fn alive_cells(app: &mut App) -> Vec<IVec2> {
+        let mut cells: Vec<_> = app
+            .world_mut()
+            .query::<(&MooreCell2d, &ConwayCellState)>()
+            .iter(app.world())
+            .filter(|(_, state)| state.0)
+            .map(|(cell, _)| cell.coords)
+            .collect();
+        cells.sort_by_key(|c| (c.x, c.y));
+        cells
+    }
+
+    fn blinker(app: &mut App) {
+        spawn_board(app, 5, &HORIZONTAL);
+        app.update();
+        assert_eq!(alive_cells(app), VERTICAL);
+        app.update();
+        assert_eq!(alive_cells(app), HORIZONTAL);
+        app.update();
+        assert_eq!(alive_cells(app), VERTICAL);
+    }
+
+    #[test]
+    fn sequential_blinker() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default());
+        blinker(&mut app);
+    }
+
+    #[test]
+    fn batched_blinker() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default())
+            .insert_resource(SimulationBatch);
+        blinker(&mut app);
+    }
+
+    #[test]
+    fn stable_cells_are_inactive() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default());
+        let block = [
+            IVec2::new(1, 1),
+            IVec2::new(1, 2),
+            IVec2::new(2, 1),
+            IVec2::new(2, 2),
+        ];
+        spawn_board(&mut app, 4, &block);
+        app.update();
+        let map = app
+            .world()
+            .resource::<CellStateMap<MooreCell2d, ConwayCellState>>();
+        assert_eq!(map.active_cells().count(), 0);
+        assert_eq!(alive_cells(&mut app), block);
+    }
+
+    #[test]
+    fn only_changed_states_are_flagged() {
+        #[derive(Resource, Default)]
+        struct ChangedCells(Vec<IVec2>);
+
+        fn collect_changed(
+            query: Query<&MooreCell2d, Changed<ConwayCellState>>,
+            mut changed: ResMut<ChangedCells>,
+        ) {
+            changed.0 = query.iter().map(|cell| cell.coords).collect();
+            changed.0.sort_by_key(|c| (c.x, c.y));
+        }
+
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default())
+            .init_resource::<ChangedCells>()
+            .add_systems(Last, collect_changed);
+        spawn_board(&mut app, 5, &HORIZONTAL);
+        app.update();
+        // The center cell is evaluated in the next generation but stays alive
+        assert!(app
+            .world()
+            .resource::<CellStateMap<MooreCell2d, ConwayCellState>>()
+            .is_active(&IVec2::new(2, 2)));
+        app.update();
+        assert_eq!(alive_cells(&mut app), HORIZONTAL);
+        assert_eq!(
+            app.world().resource::<ChangedCells>().0,
+            [
+                IVec2::new(1, 2),
+                IVec2::new(2, 1),
+                IVec2::new(2, 3),
+                IVec2::new(3, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn generation_messages() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default());
+        spawn_board(&mut app, 5, &HORIZONTAL);
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Generation>().generation(), 2);
+        let messages: Vec<_> = app
+            .world()
+            .resource::<Messages<GenerationAdvanced<MooreCell2d, ConwayCellState>>>()
+            .iter_current_update_messages()
+            .map(|m| (m.generation, m.changed_cells))
+            .collect();
+        assert_eq!(messages, vec![(2, 4)]);
+        app.insert_resource(SimulationPause);
+        app.update();
+        assert_eq!(app.world().resource::<Generation>().generation(), 2);
+    }
+
+    #[test]
+    fn paused_steps() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default())
+            .insert_resource(SimulationPause);
+        spawn_board(&mut app, 5, &HORIZONTAL);
+        app.update();
+        assert_eq!(alive_cells(&mut app), HORIZONTAL);
+        app.insert_resource(SimulationStep(1));
+        app.update();
+        assert_eq!(alive_cells(&mut app), VERTICAL);
+        app.update();
+        assert_eq!(alive_cells(&mut app), VERTICAL);
+        app.insert_resource(SimulationStep(2));
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(alive_cells(&mut app), VERTICAL);
+        assert_eq!(app.world().resource::<Generation>().generation(), 3);
+    }
+
+    #[test]
+    fn tick_budget() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default())
+            .insert_resource(SimulationTickBudget::Generations(
+                NonZeroU32::new(3).unwrap(),
+            ));
+        spawn_board(&mut app, 5, &HORIZONTAL);
+        app.update();
+        assert_eq!(alive_cells(&mut app), VERTICAL);
+        assert_eq!(app.world().resource::<Generation>().generation(), 3);
+        app.update();
+        assert_eq!(alive_cells(&mut app), HORIZONTAL);
+        assert_eq!(app.world().resource::<Generation>().generation(), 6);
+    }
+
+    #[test]
+    fn tick_budget_stable_world() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default())
+            .insert_resource(SimulationTickBudget::Duration(Duration::from_secs(30)));
+        spawn_board(&mut app, 5, &[]);
+        app.update();
+        assert_eq!(app.world().resource::<Generation>().generation(), 1);
+    }
+
+    #[test]
+    fn custom_schedule() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, ScheduleLabel)]
+        struct SimulationSchedule;
+
+        let mut app = App::new();
+        app.init_schedule(SimulationSchedule)
+            .add_plugins(GameOfLife2dPlugin::default().with_schedule(SimulationSchedule));
+        spawn_board(&mut app, 5, &HORIZONTAL);
+        app.update();
+        assert_eq!(alive_cells(&mut app), HORIZONTAL);
+        app.world_mut().run_schedule(SimulationSchedule);
+        assert_eq!(alive_cells(&mut app), VERTICAL);
+        assert_eq!(app.world().resource::<Generation>().generation(), 1);
+    }
+
+    #[test]
+    fn simulation_speed() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default())
+            .init_resource::<Time>();
+        spawn_board(&mut app, 5, &HORIZONTAL);
+        app.insert_resource(Speed::from_generations_per_second(2.));
+        app.update();
+        assert_eq!(app.world().resource::<Generation>().generation(), 0);
+        for _ in 0..5 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(100));
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Generation>().generation(), 1);
+        app.world_mut().resource_mut::<Speed>().set_interval(None);
+        app.update();
+        assert_eq!(app.world().resource::<Generation>().generation(), 2);
+    }
+
+    #[test]
+    fn boundary_conditions() {
+        let all_cells: Vec<_> = (0..3)
+            .flat_map(|x| (0..3).map(move |y| IVec2::new(x, y)))
+            .collect();
+        let corners = [
+            IVec2::new(0, 0),
+            IVec2::new(0, 2),
+            IVec2::new(2, 0),
+            IVec2::new(2, 2),
+        ];
+        // Ignored boundaries: corners have 3 neighbors and survive
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default());
+        spawn_board(&mut app, 3, &all_cells);
+        app.update();
+        assert_eq!(alive_cells(&mut app), corners);
+        // Copied cell boundaries: every cell is overcrowded
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default().with_boundary(Boundary::CopyCell));
+        spawn_board(&mut app, 3, &all_cells);
+        app.update();
+        assert!(alive_cells(&mut app).is_empty());
+        // Mirrored boundaries: a column in the middle of the world is a stable
+        // part of infinitely repeated columns
+        let column = [IVec2::new(1, 0), IVec2::new(1, 1), IVec2::new(1, 2)];
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default().with_boundary(Boundary::Mirror));
+        spawn_board(&mut app, 3, &column);
+        app.update();
+        assert_eq!(alive_cells(&mut app), column);
+        // Fixed alive boundaries: edge cells have 3 alive neighbors
+        let mut app = App::new();
+        app.add_plugins(
+            GameOfLife2dPlugin::default().with_boundary(Boundary::Fixed(ConwayCellState(true))),
+        );
+        spawn_board(&mut app, 3, &[]);
+        app.update();
+        assert_eq!(
+            alive_cells(&mut app),
+            [
+                IVec2::new(0, 1),
+                IVec2::new(1, 0),
+                IVec2::new(1, 2),
+                IVec2::new(2, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn boundary_edit() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default());
+        spawn_board(&mut app, 3, &[]);
+        app.update();
+        app.update();
+        assert!(alive_cells(&mut app).is_empty());
+        // The settled board edges are evaluated again with the new boundary
+        app.world_mut()
+            .resource_mut::<BoundaryCondition<MooreCell2d, ConwayCellState>>()
+            .boundary = Boundary::Fixed(ConwayCellState(true));
+        app.update();
+        assert_eq!(
+            alive_cells(&mut app),
+            [
+                IVec2::new(0, 1),
+                IVec2::new(1, 0),
+                IVec2::new(1, 2),
+                IVec2::new(2, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn boundary_per_simulation() {
+        type Toroidal = ToroidalCell<MooreCell2d>;
+        let mut app = App::new();
+        app.add_plugins((
+            GameOfLife2dPlugin::default().with_boundary(Boundary::dead()),
+            CellularAutomatonPlugin::<Toroidal, ConwayCellState>::default(),
+        ));
+        let moore = app
+            .world()
+            .resource::<BoundaryCondition<MooreCell2d, ConwayCellState>>();
+        assert!(matches!(
+            moore.boundary,
+            Boundary::Fixed(ConwayCellState(false))
+        ));
+        let toroidal = app
+            .world()
+            .resource::<BoundaryCondition<Toroidal, ConwayCellState>>();
+        assert!(matches!(toroidal.boundary, Boundary::Ignore));
+    }
+
+    #[test]
+    fn toroidal_blinker() {
+        type Cell = ToroidalCell<MooreCell2d>;
+        let mut app = App::new();
+        app.add_plugins(CellularAutomatonPlugin::<Cell, ConwayCellState>::default());
+        let horizontal = [IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(4, 0)];
+        let vertical = [IVec2::new(0, 0), IVec2::new(0, 1), IVec2::new(0, 4)];
+        for x in 0..5 {
+            for y in 0..5 {
+                let coords = IVec2::new(x, y);
+                app.world_mut().spawn((
+                    Cell::new(MooreCell2d::new(coords), IVec2::splat(5)),
+                    ConwayCellState(horizontal.contains(&coords)),
+                ));
+            }
+        }
+        for expected in [vertical, horizontal] {
+            app.update();
+            let mut cells: Vec<_> = app
+                .world_mut()
+                .query::<(&Cell, &ConwayCellState)>()
+                .iter(app.world())
+                .filter(|(_, state)| state.0)
+                .map(|(cell, _)| cell.coords)
+                .collect();
+            cells.sort_by_key(|c| (c.x, c.y));
+            assert_eq!(cells, expected);
+        }
+    }
+
+    #[test]
+    fn unbounded_glider() {
+        let mut app = App::new();
+        app.add_plugins(GameOfLife2dPlugin::default())
+            .insert_resource(UnboundedWorld::<MooreCell2d, ConwayCellState>::new(
+                MooreCell2d::new,
+            ));
+        let glider = [
+            IVec2::new(0, 0),
+            IVec2::new(1, 0),
+            IVec2::new(1, 2),
+            IVec2::new(2, 0),
+            IVec2::new(2, 1),
+        ];
+        for coords in glider {
+            app.world_mut()
+                .spawn((MooreCell2d::new(coords), ConwayCellState(true)));
+        }
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(alive_cells(&mut app), glider.map(|c| c + IVec2::new(1, -1)));
+        // Births and deaths within a single tick
+        app.insert_resource(SimulationTickBudget::Generations(
+            NonZeroU32::new(4).unwrap(),
+        ));
+        app.update();
+        assert_eq!(alive_cells(&mut app), glider.map(|c| c + IVec2::new(2, -2)));
+        let cell_count = app
+            .world_mut()
+            .query::<&MooreCell2d>()
+            .iter(app.world())
+            .count();
+        assert_eq!(cell_count, 5);
+        assert_eq!(
+            app.world()
+                .resource::<CellStateMap<MooreCell2d, ConwayCellState>>()
+                .len(),
+            5
+        );
+    }
+
+    /// Directional rule, cells take the state of their left neighbor
+    #[derive(Debug, Clone, PartialEq, Component)]
+    struct WindCellState(bool);
+
+    impl CellState for WindCellState {
+        fn new_cell_state<'a>(&self, _: impl Iterator<Item = &'a Self>) -> Self {
+            self.clone()
+        }
+
+        fn new_cell_state_positional<'a>(
+            &self,
+            mut neighbor_cells: impl Iterator<Item = (usize, Option<&'a Self>)>,
+        ) -> Self {
+            let left = neighbor_cells
+                .find(|(i, _)| MooreCell2d::NEIGHBOR_OFFSETS[*i] == IVec2::NEG_X)
+                .and_then(|(_, state)| state);
+            Self(left.is_some_and(|s| s.0))
+        }
+
+        #[cfg(feature = "auto-coloring")]
+        fn color(&self) -> Option<Color> {
+            None
+        }
+    }
+
+    #[test]
+    fn directional_rule() {
+        let mut app = App::new();
+        app.add_plugins(CellularAutomatonPlugin::<MooreCell2d, WindCellState>::default());
+        for x in 0..5 {
+            app.world_mut()
+                .spawn((MooreCell2d::new(IVec2::new(x, 0)), WindCellState(x == 0)));
+        }
+        for expected in 1..5 {
+            app.update();
+            let alive: Vec<_> = app
+                .world_mut()
+                .query::<(&MooreCell2d, &WindCellState)>()
+                .iter(app.world())
+                .filter(|(_, state)| state.0)
+                .map(|(cell, _)| cell.coords.x)
+                .collect();
+            assert_eq!(alive, [expected]);
+        }
+    }
+
+    /// Context rule, cells are alive when their column, stored in the state,
+    /// and the generation share the same parity
+    #[derive(Debug, Clone, PartialEq, Component)]
+    struct ParityCellState {
+        odd_column: bool,
+        alive: bool,
+    }
+
+    impl CellState for ParityCellState {
+        const NEIGHBOR_DRIVEN: bool = false;
+
+        fn new_cell_state<'a>(&self, _: impl Iterator<Item = &'a Self>) -> Self {
+            self.clone()
+        }
+
+        fn new_cell_state_with_context<'a, C: Cell>(
+            &self,
+            _: impl Iterator<Item = (usize, Option<&'a Self>)>,
+            context: &CellContext<C>,
+        ) -> Self {
+            Self {
+                odd_column: self.odd_column,
+                alive: self.odd_column != context.generation().is_multiple_of(2),
+            }
+        }
+
+        #[cfg(feature = "auto-coloring")]
+        fn color(&self) -> Option<Color> {
+            None
+        }
+    }
+
+    #[test]
+    fn context_rule() {
+        let mut app = App::new();
+        app.add_plugins(CellularAutomatonPlugin::<MooreCell2d, ParityCellState>::default());
+        for x in 0..4 {
+            app.world_mut().spawn((
+                MooreCell2d::new(IVec2::new(x, 0)),
+                ParityCellState {
+                    odd_column: x % 2 == 1,
+                    alive: false,
+                },
+            ));
+        }
+        for generation in 0..4 {
+            app.update();
+            let alive: Vec<_> = app
+                .world_mut()
+                .query::<(&MooreCell2d, &ParityCellState)>()
+                .iter(app.world())
+                .filter(|(_, state)| state.alive)
+                .map(|(cell, _)| cell.coords.x)
+                .collect();
+            let expected: Vec<_> = (0..4).filter(|x| (x + generation) % 2 == 0).collect();
+            assert_eq!(alive, expected);
+        }
+    }
+
+    /// Neighbor driven context rule, cells light up from the third generation
+    #[derive(Debug, Clone, PartialEq, Component)]
+    struct DelayedCellState(bool);
+
+    impl CellState for DelayedCellState {
+        fn new_cell_state<'a>(&self, _: impl Iterator<Item = &'a Self>) -> Self {
+            self.clone()
+        }
+
+        fn new_cell_state_with_context<'a, C: Cell>(
+            &self,
+            _: impl Iterator<Item = (usize, Option<&'a Self>)>,
+            context: &CellContext<C>,
+        ) -> Self {
+            Self(context.generation() >= 3)
+        }
+
+        #[cfg(feature = "auto-coloring")]
+        fn color(&self) -> Option<Color> {
+            None
+        }
+    }
+
+    #[test]
+    fn context_rule_on_stable_grid() {
+        let mut app = App::new();
+        app.add_plugins(CellularAutomatonPlugin::<MooreCell2d, DelayedCellState>::default());
+        for x in 0..3 {
+            app.world_mut()
+                .spawn((MooreCell2d::new(IVec2::new(x, 0)), DelayedCellState(false)));
+        }
+        let alive_count = |app: &mut App| {
+            app.world_mut()
+                .query::<&DelayedCellState>()
+                .iter(app.world())
+                .filter(|state| state.0)
+                .count()
+        };
+        for _ in 0..3 {
+            app.update();
+            assert_eq!(alive_count(&mut app), 0);
+        }
+        app.update();
+        assert_eq!(alive_count(&mut app), 3);
+    }
+
+    /// Stochastic rule, cells flip a coin every generation
+    #[derive(Debug, Clone, PartialEq, Component)]
+    struct CoinCellState(bool);
+
+    impl CellState for CoinCellState {
+        const NEIGHBOR_DRIVEN: bool = false;
+
+        fn new_cell_state<'a>(&self, _: impl Iterator<Item = &'a Self>) -> Self {
+            self.clone()
+        }
+
+        fn new_cell_state_with_context<'a, C: Cell>(
+            &self,
+            _: impl Iterator<Item = (usize, Option<&'a Self>)>,
+            context: &CellContext<C>,
+        ) -> Self {
+            Self(context.rng().chance(0.5))
+        }
+
+        #[cfg(feature = "auto-coloring")]
+        fn color(&self) -> Option<Color> {
+            None
+        }
+    }
+
+    fn coin_flips(seed: u64, batch: bool) -> Vec<(IVec2, bool)> {
+        let mut app = App::new();
+        app.add_plugins(CellularAutomatonPlugin::<MooreCell2d, CoinCellState>::default())
+            .insert_resource(SimulationRng::new(seed));
+        if batch {
+            app.insert_resource(SimulationBatch);
+        }
+        for x in 0..16 {
+            for y in 0..16 {
+                app.world_mut()
+                    .spawn((MooreCell2d::new(IVec2::new(x, y)), CoinCellState(false)));
+            }
+        }
+        for _ in 0..3 {
+            app.update();
+        }
+        let mut states: Vec<_> = app
+            .world_mut()
+            .query::<(&MooreCell2d, &CoinCellState)>()
+            .iter(app.world())
+            .map(|(cell, state)| (cell.coords, state.0))
+            .collect();
+        states.sort_by_key(|(coords, _)| (coords.x, coords.y));
+        states
+    }
+
+    #[test]
+    fn deterministic_randomness() {
+        let flips = coin_flips(42, false);
+        assert_eq!(flips, coin_flips(42, true));
+        assert_ne!(flips, coin_flips(7, false));
+        let heads = flips.iter().filter(|(_, state)| *state).count();
+        assert!((64..192).contains(&heads));
+    }
+
+    /// Weighted rule, cells take the weighted sum of their neighbor values
+    #[derive(Debug, Clone, PartialEq, Component)]
+    struct KernelCellState(f32);
+
+    impl CellState for KernelCellState {
+        const WEIGHTED: bool = true;
+
+        fn new_cell_state<'a>(&self, _: impl Iterator<Item = &'a Self>) -> Self {
+            self.clone()
+        }
+
+        fn new_cell_state_weighted<'a>(
+            &self,
+            neighbor_cells: impl Iterator<Item = (f32, Option<&'a Self>)>,
+        ) -> Self {
+            Self(
+                neighbor_cells
+                    .filter_map(|(weight, state)| Some(weight * state?.0))
+                    .sum(),
+            )
+        }
+
+        #[cfg(feature = "auto-coloring")]
+        fn color(&self) -> Option<Color> {
+            None
+        }
+    }
+
+    #[test]
+    fn weighted_rule() {
+        let mut app = App::new();
+        app.add_plugins(CellularAutomatonPlugin::<RingRangeCell2d<2>, KernelCellState>::default());
+        let center = IVec2::new(2, 2);
+        for x in 0..5 {
+            for y in 0..5 {
+                let coords = IVec2::new(x, y);
+                app.world_mut().spawn((
+                    RingRangeCell2d::<2>::new(coords),
+                    KernelCellState(if coords == center { 1.0 } else { 0.0 }),
+                ));
+            }
+        }
+        app.update();
+        let states: Vec<_> = app
+            .world_mut()
+            .query::<(&RingRangeCell2d<2>, &KernelCellState)>()
+            .iter(app.world())
+            .map(|(cell, state)| (cell.coords, state.0))
+            .collect();
+        assert_eq!(states.len(), 25);
+        for (coords, value) in states {
+            let offset = coords - center;
+            let expected = if offset.length_squared() <= 4 {
+                RingNeighborhood::weight(offset, 2)
+            } else {
+                0.0
+            };
+            assert!((value - expected).abs() < f32::EPSILON, "{coords}: {value}");
+        }
+    }
+}
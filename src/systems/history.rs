@@ -0,0 +1,130 @@
+use crate::{
+    components::{Cell1d, CellState, HistoryCell},
+    resources::{CellStateMap, SimulationGeneration, SpaceTimeHistory},
+};
+use bevy::prelude::*;
+
+/// Records the current generation of the 1D cells as a new row of
+/// [`HistoryCell`] entities, once per generation
+#[allow(clippy::needless_pass_by_value, clippy::cast_precision_loss)]
+pub fn record_history<S, const R: i32>(
+    mut commands: Commands,
+    map: Res<CellStateMap<Cell1d<R>, S>>,
+    generation: Res<SimulationGeneration<Cell1d<R>, S>>,
+    history: Res<SpaceTimeHistory<S, R>>,
+    rows: Query<(Entity, &HistoryCell), With<S>>,
+    mut recorded: Local<Option<u64>>,
+) where
+    S: CellState,
+{
+    let generation = generation.generation();
+    if map.is_empty() || *recorded == Some(generation) {
+        return;
+    }
+    *recorded = Some(generation);
+    if let Some(max_rows) = history.max_rows {
+        for (entity, cell) in &rows {
+            if generation.saturating_sub(cell.generation) >= max_rows {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+    let y = -(generation as f32) * history.cell_size;
+    for (&coords, state) in map.iter() {
+        let translation = Vec3::new(coords as f32 * history.cell_size, y, 0.0);
+        let cell = (
+            HistoryCell { coords, generation },
+            state.clone(),
+            Transform::from_translation(translation),
+        );
+        #[cfg(feature = "auto-coloring")]
+        commands.spawn((
+            cell,
+            Sprite::from_color(Color::NONE, Vec2::splat(history.cell_size)),
+            Visibility::default(),
+        ));
+        #[cfg(not(feature = "auto-coloring"))]
+        commands.spawn(cell);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell1d, Elementary1dPlugin, History1dPlugin, HistoryCell, Rule90CellState};
+    use bevy::prelude::*;
+
+    fn live_history_cells(app: &mut App) -> Vec<(u64, i32)> {
+        let mut cells: Vec<_> = app
+            .world_mut()
+            .query::<(&HistoryCell, &Rule90CellState)>()
+            .iter(app.world())
+            .filter(|(_, state)| state.0)
+            .map(|(cell, _)| (cell.generation, cell.coords))
+            .collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn rule_90_diagram() {
+        let mut app = App::new();
+        app.add_plugins((
+            Elementary1dPlugin::<90>::default(),
+            History1dPlugin::<Rule90CellState>::default().with_cell_size(2.0),
+        ));
+        for x in -3..=3 {
+            app.world_mut()
+                .spawn((Cell1d::<1>::new(x), Rule90CellState::from(x == 0)));
+        }
+        for _ in 0..4 {
+            app.update();
+        }
+        // Sierpiński triangle
+        assert_eq!(
+            live_history_cells(&mut app),
+            [
+                (0, 0),
+                (1, -1),
+                (1, 1),
+                (2, -2),
+                (2, 2),
+                (3, -3),
+                (3, -1),
+                (3, 1),
+                (3, 3),
+            ]
+        );
+        let transform = app
+            .world_mut()
+            .query::<(&HistoryCell, &Transform)>()
+            .iter(app.world())
+            .find(|(cell, _)| cell.generation == 3 && cell.coords == -1)
+            .map(|(_, transform)| transform.translation);
+        assert_eq!(transform, Some(Vec3::new(-2.0, -6.0, 0.0)));
+    }
+
+    #[test]
+    fn max_rows() {
+        let mut app = App::new();
+        app.add_plugins((
+            Elementary1dPlugin::<90>::default(),
+            History1dPlugin::<Rule90CellState>::default().with_max_rows(2),
+        ));
+        for x in -3..=3 {
+            app.world_mut()
+                .spawn((Cell1d::<1>::new(x), Rule90CellState::from(x == 0)));
+        }
+        for _ in 0..4 {
+            app.update();
+        }
+        let mut generations: Vec<_> = app
+            .world_mut()
+            .query::<&HistoryCell>()
+            .iter(app.world())
+            .map(|cell| cell.generation)
+            .collect();
+        generations.sort_unstable();
+        generations.dedup();
+        assert_eq!(generations, [2, 3]);
+    }
+}
@@ -1,3 +1,9 @@
+#[cfg(feature = "2D")]
+pub mod blocks;
 pub mod cells;
 #[cfg(feature = "auto-coloring")]
 pub mod coloring;
+#[cfg(feature = "1D")]
+pub mod history;
+pub mod sandpile;
+pub mod turmites;
@@ -0,0 +1,318 @@
+use crate::{
+    components::{Cell, SandpileCellState},
+    messages::{Avalanche, DropGrains, GenerationAdvanced},
+    resources::{CellStateMap, SandpileRelaxation, SimulationGeneration},
+    systems::cells::SimulationControl,
+};
+use bevy::{
+    platform::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    },
+    prelude::*,
+};
+
+/// Changes of a sandpile generation
+struct SandpileChanges<C: Cell> {
+    /// Changed cell entities and their new states
+    states: Vec<(Entity, SandpileCellState)>,
+    /// Coordinates of the toppled cells
+    toppled: Vec<C::Coordinates>,
+    /// Amount of grains which fell off the edges
+    lost_grains: u64,
+}
+
+/// Ongoing avalanche statistics
+pub struct AvalancheTracker<C: Cell> {
+    size: u64,
+    area: HashSet<C::Coordinates>,
+    duration: u32,
+    lost_grains: u64,
+}
+
+impl<C: Cell> Default for AvalancheTracker<C> {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            area: HashSet::new(),
+            duration: 0,
+            lost_grains: 0,
+        }
+    }
+}
+
+impl<C: Cell> AvalancheTracker<C> {
+    /// Records a generation with toppling cells
+    fn record(&mut self, changes: &SandpileChanges<C>) {
+        self.size += changes.toppled.len() as u64;
+        self.area.extend(changes.toppled.iter().cloned());
+        self.duration += 1;
+        self.lost_grains += changes.lost_grains;
+    }
+
+    /// Ends the ongoing avalanche, if any, at the stable `generation`
+    fn finish(&mut self, generation: u64) -> Option<Avalanche<C>> {
+        if self.duration == 0 {
+            return None;
+        }
+        let avalanche = std::mem::take(self);
+        Some(Avalanche::new(
+            avalanche.size,
+            avalanche.area.len(),
+            avalanche.duration,
+            avalanche.lost_grains,
+            generation,
+        ))
+    }
+}
+
+/// Topples every unstable active cell of `map`, returning the changes
+fn next_sandpile_generation<C: Cell>(
+    map: &mut CellStateMap<C, SandpileCellState>,
+) -> SandpileChanges<C> {
+    let mut toppled = Vec::new();
+    let mut lost_grains = 0;
+    // Grains received by the changed cells. Unstable cells are always active,
+    // so the changed cells topple exactly when evaluated as unstable here
+    let mut received: HashMap<C::Coordinates, u32> = HashMap::new();
+    for coords in map.take_active() {
+        let Some((cell, state)) = map.get_cell_state(&coords) else {
+            continue;
+        };
+        if !state.is_unstable(SandpileCellState::threshold(cell)) {
+            continue;
+        }
+        for neighbor in cell.neighbor_coordinates() {
+            if map.get_state(&neighbor).is_some() {
+                *received.entry(neighbor).or_default() += 1;
+            } else {
+                lost_grains += 1;
+            }
+        }
+        received.entry(coords.clone()).or_default();
+        toppled.push(coords);
+    }
+    let states = received
+        .into_iter()
+        .filter_map(|(coords, received)| {
+            let (cell, state) = map.get_cell_state(&coords)?;
+            let new_state = state.topple(SandpileCellState::threshold(cell), received);
+            let entity = map.set_state(&coords, new_state)?;
+            Some((entity, new_state))
+        })
+        .collect();
+    SandpileChanges {
+        states,
+        toppled,
+        lost_grains,
+    }
+}
+
+/// Drops the grains of the [`DropGrains`] messages on the pile
+pub fn drop_grains<C: Cell>(
+    mut drops: MessageReader<DropGrains<C>>,
+    mut states: Query<&mut SandpileCellState, With<C>>,
+    mut map: ResMut<CellStateMap<C, SandpileCellState>>,
+) {
+    for drop in drops.read() {
+        let Some(state) = map.get_state(&drop.coords) else {
+            continue;
+        };
+        let new_state = SandpileCellState(state.0.saturating_add(drop.grains));
+        if let Some(entity) = map.set_state(&drop.coords, new_state) {
+            if let Ok(mut state) = states.get_mut(entity) {
+                state.set_if_neq(new_state);
+            }
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn handle_sandpile<C: Cell>(
+    mut states: Query<&mut SandpileCellState, With<C>>,
+    mut map: ResMut<CellStateMap<C, SandpileCellState>>,
+    mut generation: ResMut<SimulationGeneration<C, SandpileCellState>>,
+    mut generation_messages: MessageWriter<GenerationAdvanced<C, SandpileCellState>>,
+    mut avalanche_messages: MessageWriter<Avalanche<C>>,
+    mut avalanche: Local<AvalancheTracker<C>>,
+    mut control: SimulationControl,
+    relaxation: Option<Res<SandpileRelaxation<C>>>,
+) {
+    if !control.should_run() {
+        return;
+    }
+    let start = Instant::now();
+    let mut generations = 0;
+    let mut new_states = HashMap::new();
+    loop {
+        let changes = next_sandpile_generation(&mut map);
+        generations += 1;
+        let generation = generation.advance();
+        generation_messages.write(GenerationAdvanced::new(generation, changes.states.len()));
+        let stable = changes.toppled.is_empty();
+        if stable {
+            if let Some(message) = avalanche.finish(generation) {
+                avalanche_messages.write(message);
+            }
+        } else {
+            avalanche.record(&changes);
+        }
+        new_states.extend(changes.states);
+        let proceed = relaxation.as_ref().map_or_else(
+            || control.should_continue(generations, start),
+            |relaxation| relaxation.should_continue(generations),
+        );
+        if stable || !proceed {
+            break;
+        }
+    }
+    for (entity, new_state) in new_states {
+        if let Ok(mut state) = states.get_mut(entity) {
+            state.set_if_neq(new_state);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "2D"))]
+mod tests {
+    use crate::{
+        Avalanche, CellularAutomatonPlugin, DropGrains, HexagonCell2d, NeumannCell2d,
+        SandpileCellState, SandpilePlugin,
+    };
+    use bevy::{ecs::message::Messages, prelude::*};
+
+    fn sandpile_app(plugin: SandpilePlugin<NeumannCell2d>, size: i32) -> App {
+        let mut app = App::new();
+        app.add_plugins(plugin);
+        for x in -size..=size {
+            for y in -size..=size {
+                app.world_mut().spawn((
+                    NeumannCell2d::new(IVec2::new(x, y)),
+                    SandpileCellState::default(),
+                ));
+            }
+        }
+        app.update();
+        app
+    }
+
+    fn grains(app: &mut App) -> Vec<(IVec2, u32)> {
+        let mut cells: Vec<_> = app
+            .world_mut()
+            .query::<(&NeumannCell2d, &SandpileCellState)>()
+            .iter(app.world())
+            .filter(|(_, state)| state.0 > 0)
+            .map(|(cell, state)| (cell.coords, state.0))
+            .collect();
+        cells.sort_unstable_by_key(|(coords, _)| (coords.x, coords.y));
+        cells
+    }
+
+    fn avalanches<C: Send + Sync + 'static>(app: &App) -> Vec<Avalanche<C>> {
+        let messages = app.world().resource::<Messages<Avalanche<C>>>();
+        messages.get_cursor().read(messages).copied().collect()
+    }
+
+    #[test]
+    fn toppling() {
+        let mut app = sandpile_app(SandpilePlugin::default(), 2);
+        app.world_mut()
+            .write_message(DropGrains::<NeumannCell2d>::new(IVec2::ZERO, 4));
+        app.update();
+        assert_eq!(
+            grains(&mut app),
+            [
+                (IVec2::new(-1, 0), 1),
+                (IVec2::new(0, -1), 1),
+                (IVec2::new(0, 1), 1),
+                (IVec2::new(1, 0), 1),
+            ]
+        );
+        assert!(avalanches::<NeumannCell2d>(&app).is_empty());
+        // The avalanche ends with the next stable generation
+        app.update();
+        let [avalanche] = avalanches::<NeumannCell2d>(&app)[..] else {
+            panic!("Expected a single avalanche");
+        };
+        assert_eq!(
+            (avalanche.size, avalanche.area, avalanche.duration),
+            (1, 1, 1)
+        );
+        assert_eq!(avalanche.lost_grains, 0);
+    }
+
+    #[test]
+    fn empty_drop() {
+        #[derive(Resource, Default)]
+        struct ChangedCells(usize);
+
+        fn count_changed(
+            query: Query<(), Changed<SandpileCellState>>,
+            mut changed: ResMut<ChangedCells>,
+        ) {
+            changed.0 = query.iter().count();
+        }
+
+        let mut app = sandpile_app(SandpilePlugin::default(), 2);
+        app.init_resource::<ChangedCells>()
+            .add_systems(Last, count_changed);
+        app.update();
+        app.world_mut()
+            .write_message(DropGrains::<NeumannCell2d>::new(IVec2::ZERO, 0));
+        app.update();
+        assert!(grains(&mut app).is_empty());
+        assert_eq!(app.world().resource::<ChangedCells>().0, 0);
+    }
+
+    #[test]
+    fn relaxation() {
+        let mut app = sandpile_app(SandpilePlugin::default().with_relaxation(), 3);
+        app.world_mut()
+            .write_message(DropGrains::<NeumannCell2d>::new(IVec2::ZERO, 16));
+        app.update();
+        // The pile is stable within a single frame, without grain loss
+        let cells = grains(&mut app);
+        assert!(cells.iter().all(|(_, grains)| *grains < 4));
+        assert_eq!(cells.iter().map(|(_, grains)| grains).sum::<u32>(), 16);
+        let [avalanche] = avalanches::<NeumannCell2d>(&app)[..] else {
+            panic!("Expected a single avalanche");
+        };
+        assert_eq!(avalanche.lost_grains, 0);
+        assert!(avalanche.duration > 1);
+        assert!(avalanche.size >= u64::from(avalanche.duration));
+        // Stable frames have no avalanche
+        app.update();
+        app.update();
+        assert!(avalanches::<NeumannCell2d>(&app).is_empty());
+    }
+
+    #[test]
+    fn lost_grains() {
+        let mut app = App::new();
+        app.add_plugins(SandpilePlugin::<HexagonCell2d>::default().with_relaxation());
+        app.world_mut()
+            .spawn((HexagonCell2d::new(IVec3::ZERO), SandpileCellState(13)));
+        app.update();
+        let [avalanche] = avalanches::<HexagonCell2d>(&app)[..] else {
+            panic!("Expected a single avalanche");
+        };
+        assert_eq!((avalanche.size, avalanche.area), (2, 1));
+        assert_eq!(avalanche.lost_grains, 12);
+        let state = app
+            .world_mut()
+            .query::<&SandpileCellState>()
+            .single(app.world())
+            .copied()
+            .unwrap();
+        assert_eq!(state, SandpileCellState(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicts with another plugin")]
+    fn conflicting_plugins() {
+        App::new().add_plugins((
+            CellularAutomatonPlugin::<NeumannCell2d, SandpileCellState>::default(),
+            SandpilePlugin::<NeumannCell2d>::default(),
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    components::{Turmite, TurmiteCell, TurmiteState},
+    messages::GenerationAdvanced,
+    resources::CellStateMap,
+};
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Moves every [`Turmite`] by one step for every generation the simulation
+/// advanced this tick, painting the cells through the [`CellStateMap`].
+pub fn handle_turmites<C, S>(
+    mut turmites: Query<&mut Turmite<C>>,
+    mut states: Query<&mut S, With<C>>,
+    mut map: ResMut<CellStateMap<C, S>>,
+    mut generations: MessageReader<GenerationAdvanced<C, S>>,
+) where
+    C: TurmiteCell,
+    S: TurmiteState,
+{
+    let steps = generations.read().count();
+    if turmites.is_empty() || steps == 0 {
+        return;
+    }
+    let mut new_states = HashMap::new();
+    for _ in 0..steps {
+        for mut turmite in &mut turmites {
+            let coords = turmite.coords.clone();
+            let current = map.get_state(&coords);
+            let color = turmite.step(current.map_or(0, S::turmite_color));
+            let new_state = S::from_turmite_color(color);
+            if current.is_none_or(|state| state == &new_state) {
+                continue;
+            }
+            if let Some(entity) = map.set_state(&coords, new_state.clone()) {
+                new_states.insert(entity, new_state);
+            }
+        }
+    }
+    for (entity, new_state) in new_states {
+        if let Ok(mut state) = states.get_mut(entity) {
+            state.set_if_neq(new_state);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "2D"))]
+mod tests {
+    use crate::{
+        CellularAutomatonPlugin, HexagonCell2d, NeumannCell2d, SimulationPause, SimulationStep,
+        Turmite, TurmiteCellState, TurmitePlugin, TurmiteRule,
+    };
+    use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+    fn painted_cells(app: &mut App) -> Vec<(IVec2, u8)> {
+        let mut cells: Vec<_> = app
+            .world_mut()
+            .query::<(&NeumannCell2d, &TurmiteCellState)>()
+            .iter(app.world())
+            .filter(|(_, state)| state.0 != 0)
+            .map(|(cell, state)| (cell.coords, state.0))
+            .collect();
+        cells.sort_unstable_by_key(|(coords, _)| (coords.x, coords.y));
+        cells
+    }
+
+    fn turmite(app: &mut App) -> Turmite<NeumannCell2d> {
+        app.world_mut()
+            .query::<&Turmite<NeumannCell2d>>()
+            .single(app.world())
+            .unwrap()
+            .clone()
+    }
+
+    fn ant_app(rule: &str) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            CellularAutomatonPlugin::<NeumannCell2d, TurmiteCellState>::default(),
+            TurmitePlugin::<NeumannCell2d>::default(),
+        ));
+        for x in -5..=5 {
+            for y in -5..=5 {
+                app.world_mut().spawn((
+                    NeumannCell2d::new(IVec2::new(x, y)),
+                    TurmiteCellState::default(),
+                ));
+            }
+        }
+        app.world_mut().spawn(Turmite::<NeumannCell2d>::new(
+            IVec2::ZERO,
+            rule.parse().unwrap(),
+        ));
+        app
+    }
+
+    #[test]
+    fn langtons_ant() {
+        let mut app = ant_app("RL");
+        for _ in 0..4 {
+            app.update();
+        }
+        // The ant walked a clockwise square back to its start
+        let ant = turmite(&mut app);
+        assert_eq!((ant.coords, ant.heading), (IVec2::ZERO, 0));
+        assert_eq!(
+            painted_cells(&mut app),
+            [
+                (IVec2::new(0, 0), 1),
+                (IVec2::new(0, 1), 1),
+                (IVec2::new(1, 0), 1),
+                (IVec2::new(1, 1), 1),
+            ]
+        );
+        // The start cell is painted back and the ant turns left
+        app.update();
+        let ant = turmite(&mut app);
+        assert_eq!((ant.coords, ant.heading), (IVec2::NEG_Y, 3));
+        assert_eq!(painted_cells(&mut app).len(), 3);
+    }
+
+    #[test]
+    fn multi_color_ant() {
+        let mut app = ant_app("RRL");
+        app.insert_resource(SimulationPause)
+            .insert_resource(SimulationStep(1));
+        for _ in 0..3 {
+            app.update();
+        }
+        // A single step while paused
+        let ant = turmite(&mut app);
+        assert_eq!((ant.coords, ant.heading), (IVec2::Y, 1));
+        assert_eq!(painted_cells(&mut app), [(IVec2::ZERO, 1)]);
+        app.world_mut().remove_resource::<SimulationPause>();
+        for _ in 0..4 {
+            app.update();
+        }
+        // The start cell is painted with the second color
+        assert!(painted_cells(&mut app).contains(&(IVec2::ZERO, 2)));
+    }
+
+    #[test]
+    fn custom_schedule() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, ScheduleLabel)]
+        struct SimulationSchedule;
+
+        let mut app = App::new();
+        app.init_schedule(SimulationSchedule).add_plugins((
+            CellularAutomatonPlugin::<NeumannCell2d, TurmiteCellState>::default()
+                .with_schedule(SimulationSchedule),
+            TurmitePlugin::<NeumannCell2d>::default(),
+        ));
+        app.world_mut()
+            .spawn((NeumannCell2d::new(IVec2::ZERO), TurmiteCellState::default()));
+        app.world_mut().spawn(Turmite::<NeumannCell2d>::new(
+            IVec2::ZERO,
+            "RL".parse().unwrap(),
+        ));
+        app.update();
+        assert_eq!(turmite(&mut app).coords, IVec2::ZERO);
+        app.world_mut().run_schedule(SimulationSchedule);
+        assert_eq!(turmite(&mut app).coords, IVec2::Y);
+    }
+
+    #[test]
+    #[should_panic(expected = "TurmitePlugin requires a CellularAutomatonPlugin")]
+    fn missing_simulation() {
+        App::new().add_plugins(TurmitePlugin::<NeumannCell2d>::default());
+    }
+
+    #[test]
+    fn hexagonal_ant() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellularAutomatonPlugin::<HexagonCell2d, TurmiteCellState>::default(),
+            TurmitePlugin::<HexagonCell2d>::default(),
+        ));
+        for coords in [IVec3::ZERO, IVec3::new(1, 0, -1)] {
+            app.world_mut()
+                .spawn((HexagonCell2d::new(coords), TurmiteCellState::default()));
+        }
+        app.world_mut().spawn(Turmite::<HexagonCell2d>::new(
+            IVec3::ZERO,
+            TurmiteRule::parse("R1R2").unwrap(),
+        ));
+        app.update();
+        app.update();
+        let ant = app
+            .world_mut()
+            .query::<&Turmite<HexagonCell2d>>()
+            .single(app.world())
+            .unwrap()
+            .clone();
+        assert_eq!((ant.coords, ant.heading), (IVec3::new(2, -1, -1), 2));
+    }
+}